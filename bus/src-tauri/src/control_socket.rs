@@ -0,0 +1,225 @@
+//! Control Socket Module
+//!
+//! A local control-plane IPC server that lets an out-of-process client
+//! (e.g. a `kamune-cli` binary) drive any daemon connection the GUI has
+//! open, instead of spawning a second daemon instance. Listens on a Unix
+//! domain socket (a Windows named pipe on Windows) and multiplexes any
+//! number of simultaneous clients onto the shared [`DaemonManager`].
+
+use crate::daemon_bridge::DaemonCommand;
+use crate::daemon_manager::{DaemonId, DaemonManager};
+use crate::CommandResponse;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+/// Commands an out-of-process client may issue over the control socket.
+/// Deliberately a subset of the commands the Tauri frontend exposes -
+/// the socket multiplexes existing automation onto the manager's
+/// connections, it doesn't widen what a local process can ask them to do.
+const ALLOWED_COMMANDS: &[&str] = &[
+    "start_server",
+    "dial",
+    "send_message",
+    "list_sessions",
+    "close_session",
+];
+
+/// The one control-socket verb handled outside `ALLOWED_COMMANDS`: instead
+/// of issuing a `DaemonCommand` and waiting for one response, it opens a
+/// filtered tap onto the connection's event bus and streams every matching
+/// event back as its own response line until the client disconnects or
+/// sends an `"unsubscribe"` line.
+const SUBSCRIBE_COMMAND: &str = "subscribe";
+
+/// One control-socket request: the connection to target, a `DaemonCommand`
+/// name, and its params, read as a single newline-delimited JSON line.
+#[derive(Debug, serde::Deserialize)]
+struct ControlRequest {
+    connection_id: String,
+    cmd: String,
+    #[serde(default)]
+    params: serde_json::Value,
+    /// Overrides the default command deadline, if given.
+    #[serde(default)]
+    timeout_ms: Option<u64>,
+    /// `evt` names to restrict delivery to, for a `"subscribe"` request.
+    /// Ignored by every other command.
+    #[serde(default)]
+    filter: Option<Vec<String>>,
+}
+
+/// Default path the control socket listens on.
+#[cfg(unix)]
+pub fn default_socket_path(runtime_dir: &Path) -> PathBuf {
+    runtime_dir.join("kamune-control.sock")
+}
+
+/// Default name the control socket's named pipe listens on.
+#[cfg(windows)]
+pub fn default_pipe_name() -> String {
+    r"\\.\pipe\kamune-control".to_string()
+}
+
+/// Bind the control socket and serve clients until the process exits or
+/// binding fails. Per-connection errors are logged and only close that
+/// connection.
+#[cfg(unix)]
+pub async fn serve(manager: DaemonManager, socket_path: PathBuf) -> std::io::Result<()> {
+    // A stale socket file left behind by a crashed previous run would
+    // otherwise make binding fail with "address in use".
+    let _ = std::fs::remove_file(&socket_path);
+
+    let listener = tokio::net::UnixListener::bind(&socket_path)?;
+    info!("Control socket listening on {:?}", socket_path);
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let manager = manager.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, manager).await {
+                warn!("Control socket connection error: {}", e);
+            }
+        });
+    }
+}
+
+/// Bind the control socket and serve clients until the process exits or
+/// binding fails. Per-connection errors are logged and only close that
+/// connection.
+#[cfg(windows)]
+pub async fn serve(manager: DaemonManager, pipe_name: String) -> std::io::Result<()> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    info!("Control socket listening on {}", pipe_name);
+
+    let mut server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .create(&pipe_name)?;
+
+    loop {
+        server.connect().await?;
+        let connected = server;
+        server = ServerOptions::new().create(&pipe_name)?;
+
+        let manager = manager.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(connected, manager).await {
+                warn!("Control socket connection error: {}", e);
+            }
+        });
+    }
+}
+
+async fn handle_connection<S>(stream: S, manager: DaemonManager) -> std::io::Result<()>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let (read_half, mut write_half) = tokio::io::split(stream);
+    let mut lines = BufReader::new(read_half).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let request = match serde_json::from_str::<ControlRequest>(&line) {
+            Ok(request) => request,
+            Err(e) => {
+                write_response(
+                    &mut write_half,
+                    CommandResponse::error(&format!("invalid request: {}", e)),
+                )
+                .await?;
+                continue;
+            }
+        };
+
+        if request.cmd == SUBSCRIBE_COMMAND {
+            stream_subscription(&manager, request, &mut lines, &mut write_half).await?;
+            continue;
+        }
+
+        let response = dispatch(&manager, request).await;
+        write_response(&mut write_half, response).await?;
+    }
+
+    Ok(())
+}
+
+async fn dispatch(manager: &DaemonManager, request: ControlRequest) -> CommandResponse {
+    if !ALLOWED_COMMANDS.contains(&request.cmd.as_str()) {
+        return CommandResponse::error(&format!(
+            "command not allowed over control socket: {}",
+            request.cmd
+        ));
+    }
+
+    let id = DaemonId::from_string(request.connection_id);
+    let cmd = DaemonCommand::new(&request.cmd, request.params);
+    let timeout = request.timeout_ms.map(Duration::from_millis);
+
+    match manager.send_to(&id, cmd, timeout).await {
+        Ok(event) => CommandResponse::success(event.data),
+        Err(e) => CommandResponse::daemon_error(&e),
+    }
+}
+
+/// Handle a `"subscribe"` request: open a filtered tap onto the
+/// connection's event bus via [`DaemonManager::subscribe`] and write every
+/// matching event back as its own response line, until the client sends an
+/// `"unsubscribe"` line or disconnects.
+async fn stream_subscription<R, W>(
+    manager: &DaemonManager,
+    request: ControlRequest,
+    lines: &mut tokio::io::Lines<BufReader<R>>,
+    write_half: &mut W,
+) -> std::io::Result<()>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin,
+{
+    let id = DaemonId::from_string(request.connection_id);
+    let mut events = match manager.subscribe(&id, request.filter).await {
+        Ok(rx) => rx,
+        Err(e) => return write_response(write_half, CommandResponse::daemon_error(&e)).await,
+    };
+
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                match event {
+                    Ok(event) => {
+                        let data = serde_json::to_value(&event).unwrap_or_default();
+                        write_response(write_half, CommandResponse::success(data)).await?;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                }
+            }
+            line = lines.next_line() => {
+                match line? {
+                    Some(l) if l.trim() == "unsubscribe" => break,
+                    Some(_) => continue,
+                    None => break,
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Serialize and write `response` as a single newline-terminated JSON line.
+async fn write_response<W: tokio::io::AsyncWrite + Unpin>(
+    write_half: &mut W,
+    response: CommandResponse,
+) -> std::io::Result<()> {
+    let mut json = serde_json::to_string(&response).unwrap_or_else(|_| {
+        r#"{"success":false,"data":null,"error":{"code":"internal","message":"failed to serialize response"}}"#.to_string()
+    });
+    json.push('\n');
+    write_half.write_all(json.as_bytes()).await
+}
@@ -4,15 +4,188 @@
 //! communication between the Tauri frontend and the daemon via JSON-over-stdio.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::io::{BufRead, BufReader, Write};
-use std::path::PathBuf;
-use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
-use tokio::sync::{mpsc, oneshot, Mutex, RwLock};
+use tokio::sync::{broadcast, mpsc, oneshot, Mutex, RwLock};
 use tracing::{debug, error, info, warn};
 
+/// Protocol version spoken by this client. Bumped whenever a
+/// backwards-incompatible change is made to the stdio wire format.
+const CLIENT_PROTOCOL_VERSION: u32 = 1;
+
+/// How long `spawn` waits for the daemon to complete the hello handshake
+/// before giving up.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long [`DaemonBridge::attach`] waits for the transport-level
+/// connection to an already-running daemon before giving up, so an
+/// unreachable endpoint can't hang the calling thread indefinitely.
+const ATTACH_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often the supervisor polls the child process for liveness.
+const SUPERVISOR_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Base delay for the supervisor's exponential backoff (doubles each
+/// attempt, capped at `SUPERVISOR_MAX_BACKOFF`).
+const SUPERVISOR_BASE_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Ceiling on the supervisor's restart backoff.
+const SUPERVISOR_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Maximum number of consecutive unexpected-exit restarts before the
+/// supervisor gives up on the daemon.
+const SUPERVISOR_MAX_RESTARTS: u32 = 10;
+
+/// Default per-command timeout used when callers don't specify one.
+pub const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Buffer size of each subscriber's broadcast channel. A slow subscriber
+/// that falls this far behind starts missing events rather than stalling
+/// the reader thread.
+const SUBSCRIBER_CHANNEL_CAPACITY: usize = 256;
+
+/// Size of each chunk [`DaemonBridge::send_file`] reads from disk and
+/// writes to the daemon's stdin.
+const FILE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A registered event-bus subscriber: an optional set of `evt` names to
+/// restrict delivery to, paired with the channel events are sent on.
+type Subscriber = (Option<HashSet<String>>, broadcast::Sender<DaemonEvent>);
+
+/// Largest single frame accepted in [`FramingMode::LengthPrefixed`] mode.
+/// Guards against a corrupt or malicious length prefix causing an
+/// unbounded allocation.
+const MAX_FRAME_SIZE: u32 = 16 * 1024 * 1024;
+
+/// Wire transport used for the stdio bridge, chosen when the daemon is
+/// spawned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FramingMode {
+    /// One JSON message per newline-terminated line (the original
+    /// transport). Breaks down for payloads containing embedded newlines
+    /// or large/binary blobs.
+    #[default]
+    LineJson,
+    /// Each message is a 4-byte big-endian length followed by exactly
+    /// that many bytes of JSON payload, on both stdin and stdout.
+    LengthPrefixed,
+}
+
+/// A control endpoint a daemon may be reached at, for [`DaemonBridge::attach`]
+/// to an already-running process instead of spawning a new one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlEndpoint {
+    /// A Unix domain socket path. Not available on Windows.
+    Unix(PathBuf),
+    /// A TCP address, e.g. `127.0.0.1:4242`.
+    Tcp(SocketAddr),
+}
+
+impl ControlEndpoint {
+    /// Parse an endpoint from its persisted string form: a bare path is
+    /// treated as a Unix socket, anything that parses as a socket address
+    /// is treated as TCP.
+    pub fn parse(s: &str) -> Result<Self, DaemonError> {
+        if let Ok(addr) = s.parse::<SocketAddr>() {
+            return Ok(Self::Tcp(addr));
+        }
+        if s.is_empty() {
+            return Err(DaemonError::InvalidEndpoint(
+                "empty control endpoint".to_string(),
+            ));
+        }
+        Ok(Self::Unix(PathBuf::from(s)))
+    }
+}
+
+impl std::fmt::Display for ControlEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unix(path) => write!(f, "{}", path.display()),
+            Self::Tcp(addr) => write!(f, "{}", addr),
+        }
+    }
+}
+
+/// Probe whether a live daemon answers at `endpoint`, without performing
+/// the full handshake.
+pub fn probe_endpoint(endpoint: &ControlEndpoint) -> bool {
+    match endpoint {
+        ControlEndpoint::Tcp(addr) => {
+            TcpStream::connect_timeout(addr, Duration::from_millis(500)).is_ok()
+        }
+        #[cfg(unix)]
+        ControlEndpoint::Unix(path) => {
+            // `UnixStream::connect` has no built-in timeout, so race it on
+            // a helper thread against the same 500ms bound the `Tcp` arm
+            // gets, rather than trusting the OS to fail fast against an
+            // unreachable or non-listening socket path.
+            let path = path.clone();
+            let (tx, rx) = std::sync::mpsc::channel();
+            std::thread::spawn(move || {
+                let _ = tx.send(std::os::unix::net::UnixStream::connect(&path).is_ok());
+            });
+            rx.recv_timeout(Duration::from_millis(500)).unwrap_or(false)
+        }
+        #[cfg(not(unix))]
+        ControlEndpoint::Unix(_) => false,
+    }
+}
+
+/// Backoff delay before the supervisor's `attempt`'th restart (0-indexed),
+/// doubling each attempt and capped at [`SUPERVISOR_MAX_BACKOFF`].
+fn supervisor_backoff(attempt: u32) -> Duration {
+    (SUPERVISOR_BASE_BACKOFF * 2u32.pow(attempt.min(7))).min(SUPERVISOR_MAX_BACKOFF)
+}
+
+/// Connect to `endpoint`, bounding the wait by [`ATTACH_CONNECT_TIMEOUT`]
+/// so an unreachable daemon can't hang the calling thread indefinitely.
+fn connect_endpoint(
+    endpoint: &ControlEndpoint,
+) -> Result<(Box<dyn Write + Send>, Box<dyn Read + Send>), DaemonError> {
+    match endpoint {
+        ControlEndpoint::Tcp(addr) => {
+            let stream = TcpStream::connect_timeout(addr, ATTACH_CONNECT_TIMEOUT)
+                .map_err(DaemonError::SpawnError)?;
+            let read_stream = stream.try_clone().map_err(DaemonError::SpawnError)?;
+            Ok((Box::new(stream), Box::new(read_stream)))
+        }
+        #[cfg(unix)]
+        ControlEndpoint::Unix(path) => {
+            // `UnixStream` has no `connect_timeout` in std, so race the
+            // connect on a helper thread against the timeout the same way
+            // `handshake` races its response wait.
+            let path = path.clone();
+            let (tx, rx) = std::sync::mpsc::channel();
+            std::thread::spawn(move || {
+                let _ = tx.send(std::os::unix::net::UnixStream::connect(&path));
+            });
+            let stream = rx
+                .recv_timeout(ATTACH_CONNECT_TIMEOUT)
+                .map_err(|_| {
+                    DaemonError::SpawnError(std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        "timed out connecting to unix socket",
+                    ))
+                })?
+                .map_err(DaemonError::SpawnError)?;
+            let read_stream = stream.try_clone().map_err(DaemonError::SpawnError)?;
+            Ok((Box::new(stream), Box::new(read_stream)))
+        }
+        #[cfg(not(unix))]
+        ControlEndpoint::Unix(_) => Err(DaemonError::InvalidEndpoint(
+            "unix sockets are not supported on this platform".to_string(),
+        )),
+    }
+}
+
 /// Errors that can occur in the daemon bridge
 #[derive(Error, Debug)]
 pub enum DaemonError {
@@ -32,6 +205,45 @@ pub enum DaemonError {
     Timeout,
     #[error("daemon error: {0}")]
     DaemonError(String),
+    #[error("protocol mismatch: client speaks v{client}, daemon speaks v{daemon}")]
+    ProtocolMismatch { client: u32, daemon: u32 },
+    #[error("command not supported by daemon: {0}")]
+    Unsupported(String),
+    #[error("command was cancelled")]
+    Cancelled,
+    #[error("frame of {0} bytes exceeds max frame size")]
+    FrameTooLarge(u32),
+    #[error("invalid control endpoint: {0}")]
+    InvalidEndpoint(String),
+    #[error("file transfer error: {0}")]
+    Transfer(String),
+    #[error("peer rejected request: {0}")]
+    PeerRejected(String),
+}
+
+impl DaemonError {
+    /// A stable, machine-readable identifier for this error's category,
+    /// independent of the human-readable message in [`Display`]. Frontends
+    /// should switch on this instead of matching against `to_string()`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotRunning => "daemon_not_running",
+            Self::AlreadyRunning => "daemon_already_running",
+            Self::SpawnError(_) => "spawn_failed",
+            Self::BinaryNotFound(_) => "binary_not_found",
+            Self::SendError(_) => "send_failed",
+            Self::JsonError(_) => "protocol",
+            Self::Timeout => "timeout",
+            Self::DaemonError(_) => "daemon_error",
+            Self::ProtocolMismatch { .. } => "protocol_mismatch",
+            Self::Unsupported(_) => "unsupported",
+            Self::Cancelled => "cancelled",
+            Self::FrameTooLarge(_) => "frame_too_large",
+            Self::InvalidEndpoint(_) => "invalid_endpoint",
+            Self::Transfer(_) => "transfer_error",
+            Self::PeerRejected(_) => "peer_rejected",
+        }
+    }
 }
 
 /// Command sent to the daemon
@@ -69,13 +281,94 @@ pub struct DaemonEvent {
 /// Pending command awaiting response
 type PendingResponse = oneshot::Sender<Result<DaemonEvent, DaemonError>>;
 
+/// Open streaming session awaiting further events keyed by command id
+type PendingStream = mpsc::UnboundedSender<DaemonEvent>;
+
+/// A handle to an open bidirectional streaming command session (e.g. an
+/// interactive shell/PTY), returned by [`DaemonBridge::open_stream`].
+///
+/// Events tagged with the stream's id are delivered on the receiver
+/// returned alongside this handle until a terminal `stream-end` event
+/// arrives. Use [`StreamHandle::write`] to send further stdin for the
+/// session and [`StreamHandle::close`] to cancel it.
+pub struct StreamHandle {
+    id: String,
+    bridge: SharedDaemonBridge,
+}
+
+impl StreamHandle {
+    /// The command id this stream was opened with.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Frame and write `data` as stdin for this stream.
+    pub async fn write(&self, data: &[u8]) -> Result<(), DaemonError> {
+        let mut bridge = self.bridge.lock().await;
+        bridge.write_stream_frame(&self.id, data)
+    }
+
+    /// Cancel the stream, asking the daemon to tear down the underlying
+    /// process/session.
+    pub async fn close(&self) -> Result<(), DaemonError> {
+        let mut bridge = self.bridge.lock().await;
+        bridge.write_stream_control(&self.id, "cancel")
+    }
+
+    /// Resize the PTY backing this stream, if it was opened in PTY mode.
+    pub async fn resize(&self, cols: u16, rows: u16) -> Result<(), DaemonError> {
+        let mut bridge = self.bridge.lock().await;
+        bridge.write_stream_resize(&self.id, cols, rows)
+    }
+}
+
+/// State for one in-progress incoming file transfer: chunks tagged with
+/// the transfer's id are appended to `file` in arrival order until
+/// `received_chunks` reaches `total_chunks`.
+struct IncomingTransfer {
+    file: std::fs::File,
+    dest_path: PathBuf,
+    received_bytes: u64,
+    received_chunks: u64,
+    total_chunks: u64,
+}
+
 /// The daemon bridge manages communication with the Go daemon process
 pub struct DaemonBridge {
     child: Option<Child>,
-    stdin: Option<ChildStdin>,
+    stdin: Option<Box<dyn Write + Send>>,
     pending_responses: Arc<RwLock<HashMap<String, PendingResponse>>>,
+    pending_streams: Arc<RwLock<HashMap<String, PendingStream>>>,
+    subscribers: Arc<RwLock<Vec<Subscriber>>>,
+    /// In-progress incoming file transfers, keyed by transfer id.
+    transfers: Arc<RwLock<HashMap<String, IncomingTransfer>>>,
     event_tx: Option<mpsc::UnboundedSender<DaemonEvent>>,
     shutdown_tx: Option<oneshot::Sender<()>>,
+    /// Protocol version negotiated with the daemon during the hello
+    /// handshake. `None` until a handshake has completed.
+    protocol_version: Option<u32>,
+    /// Command names the daemon advertised support for during the hello
+    /// handshake. `None` until a handshake has completed, in which case no
+    /// capability gating is applied.
+    capabilities: Option<HashSet<String>>,
+    /// Path used to launch the daemon, remembered so the supervisor can
+    /// respawn it after an unexpected exit.
+    daemon_path: Option<PathBuf>,
+    /// Exit code observed the last time `is_running` noticed the child had
+    /// exited, surfaced to the supervisor's `daemon-restarted` event.
+    last_exit_code: Option<i32>,
+    /// Set just before an intentional `stop()`, so the supervisor thread
+    /// knows not to treat the resulting exit as a crash to recover from.
+    intentional_shutdown: Arc<AtomicBool>,
+    /// Transport used for the current (or most recently spawned) daemon
+    /// process.
+    framing: FramingMode,
+    /// Whether this bridge owns the daemon's process lifecycle. `true` for
+    /// a daemon started with `spawn`/`spawn_framed`, `false` for one
+    /// reached via [`attach`](Self::attach): an attached bridge never
+    /// sends `shutdown` or kills anything on `stop()`, since some other
+    /// process is responsible for it.
+    owns_process: bool,
 }
 
 impl DaemonBridge {
@@ -85,11 +378,44 @@ impl DaemonBridge {
             child: None,
             stdin: None,
             pending_responses: Arc::new(RwLock::new(HashMap::new())),
+            pending_streams: Arc::new(RwLock::new(HashMap::new())),
+            subscribers: Arc::new(RwLock::new(Vec::new())),
+            transfers: Arc::new(RwLock::new(HashMap::new())),
             event_tx: None,
             shutdown_tx: None,
+            protocol_version: None,
+            capabilities: None,
+            daemon_path: None,
+            last_exit_code: None,
+            intentional_shutdown: Arc::new(AtomicBool::new(false)),
+            framing: FramingMode::default(),
+            owns_process: true,
         }
     }
 
+    /// The protocol version negotiated with the daemon, if a handshake has
+    /// completed.
+    pub fn protocol_version(&self) -> Option<u32> {
+        self.protocol_version
+    }
+
+    /// Whether the daemon has advertised support for `cmd`. Returns `true`
+    /// when no handshake has taken place yet, since we have no capability
+    /// information to gate on.
+    pub fn supports(&self, cmd: &str) -> bool {
+        match &self.capabilities {
+            Some(caps) => caps.contains(cmd),
+            None => true,
+        }
+    }
+
+    fn check_capability(&self, cmd: &str) -> Result<(), DaemonError> {
+        if !self.supports(cmd) {
+            return Err(DaemonError::Unsupported(cmd.to_string()));
+        }
+        Ok(())
+    }
+
     /// Find the daemon binary path
     pub fn find_daemon_binary(resource_dir: Option<PathBuf>) -> Result<PathBuf, DaemonError> {
         // Try multiple locations in order of priority
@@ -164,17 +490,31 @@ impl DaemonBridge {
         candidates
     }
 
-    /// Spawn the daemon process
+    /// Spawn the daemon process using the line-delimited JSON transport.
     pub fn spawn(
         &mut self,
         daemon_path: PathBuf,
         event_tx: mpsc::UnboundedSender<DaemonEvent>,
+    ) -> Result<(), DaemonError> {
+        self.spawn_framed(daemon_path, event_tx, FramingMode::LineJson)
+    }
+
+    /// Spawn the daemon process, choosing the stdio wire transport.
+    pub fn spawn_framed(
+        &mut self,
+        daemon_path: PathBuf,
+        event_tx: mpsc::UnboundedSender<DaemonEvent>,
+        framing: FramingMode,
     ) -> Result<(), DaemonError> {
         if self.child.is_some() {
             return Err(DaemonError::AlreadyRunning);
         }
 
-        info!("Spawning daemon from: {:?}", daemon_path);
+        info!("Spawning daemon from: {:?} ({:?})", daemon_path, framing);
+        self.daemon_path = Some(daemon_path.clone());
+        self.framing = framing;
+        self.owns_process = true;
+        self.intentional_shutdown.store(false, Ordering::SeqCst);
 
         let mut child = Command::new(&daemon_path)
             .stdin(Stdio::piped())
@@ -192,15 +532,26 @@ impl DaemonBridge {
 
         let (shutdown_tx, shutdown_rx) = oneshot::channel();
         self.shutdown_tx = Some(shutdown_tx);
-        self.stdin = Some(stdin);
+        self.stdin = Some(Box::new(stdin));
         self.event_tx = Some(event_tx.clone());
         self.child = Some(child);
 
         // Spawn stdout reader task
         let pending = Arc::clone(&self.pending_responses);
+        let streams = Arc::clone(&self.pending_streams);
+        let subscribers = Arc::clone(&self.subscribers);
+        let transfers = Arc::clone(&self.transfers);
         let event_tx_clone = event_tx.clone();
         std::thread::spawn(move || {
-            Self::read_stdout(stdout, pending, event_tx_clone);
+            Self::read_stdout(
+                Box::new(stdout),
+                framing,
+                pending,
+                streams,
+                subscribers,
+                transfers,
+                event_tx_clone,
+            );
         });
 
         // Spawn stderr reader task (for logging)
@@ -222,12 +573,336 @@ impl DaemonBridge {
         });
 
         info!("Daemon spawned successfully");
+
+        // Block until the daemon completes the version/capability handshake
+        // so a misbehaving or incompatible binary fails fast instead of
+        // hanging the first real command.
+        self.handshake()?;
+
+        Ok(())
+    }
+
+    /// Connect to a daemon that is already running at `endpoint` rather
+    /// than spawning a new one, reusing the same handshake/read/write
+    /// pipeline as [`spawn_framed`](Self::spawn_framed). The bridge does
+    /// not own the remote process: `stop()` disconnects without sending a
+    /// `shutdown` command or killing anything.
+    pub fn attach(
+        &mut self,
+        endpoint: &ControlEndpoint,
+        event_tx: mpsc::UnboundedSender<DaemonEvent>,
+        framing: FramingMode,
+    ) -> Result<(), DaemonError> {
+        if self.child.is_some() || self.stdin.is_some() {
+            return Err(DaemonError::AlreadyRunning);
+        }
+
+        info!("Attaching to daemon at: {} ({:?})", endpoint, framing);
+
+        let (write_half, read_half) = connect_endpoint(endpoint)?;
+
+        self.framing = framing;
+        self.owns_process = false;
+        self.daemon_path = None;
+        self.intentional_shutdown.store(false, Ordering::SeqCst);
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        self.shutdown_tx = Some(shutdown_tx);
+        self.stdin = Some(write_half);
+        self.event_tx = Some(event_tx.clone());
+
+        let pending = Arc::clone(&self.pending_responses);
+        let streams = Arc::clone(&self.pending_streams);
+        let subscribers = Arc::clone(&self.subscribers);
+        let transfers = Arc::clone(&self.transfers);
+        let event_tx_clone = event_tx.clone();
+        std::thread::spawn(move || {
+            Self::read_stdout(
+                read_half,
+                framing,
+                pending,
+                streams,
+                subscribers,
+                transfers,
+                event_tx_clone,
+            );
+        });
+
+        let pending_shutdown = Arc::clone(&self.pending_responses);
+        std::thread::spawn(move || {
+            let _ = shutdown_rx;
+            if let Ok(mut pending) = pending_shutdown.try_write() {
+                for (_, sender) in pending.drain() {
+                    let _ = sender.send(Err(DaemonError::NotRunning));
+                }
+            }
+        });
+
+        self.handshake()?;
+
+        Ok(())
+    }
+
+    /// Spawn the daemon and hand supervision of it to a background thread:
+    /// if the process exits without an intentional `stop()`, it is
+    /// automatically respawned with exponential backoff.
+    pub fn spawn_supervised(
+        bridge: SharedDaemonBridge,
+        daemon_path: PathBuf,
+        event_tx: mpsc::UnboundedSender<DaemonEvent>,
+    ) -> Result<(), DaemonError> {
+        Self::spawn_supervised_framed(bridge, daemon_path, event_tx, FramingMode::LineJson)
+    }
+
+    /// Like [`spawn_supervised`](Self::spawn_supervised), but choosing the
+    /// stdio wire transport. The supervisor respawns with the same
+    /// framing on every restart.
+    pub fn spawn_supervised_framed(
+        bridge: SharedDaemonBridge,
+        daemon_path: PathBuf,
+        event_tx: mpsc::UnboundedSender<DaemonEvent>,
+        framing: FramingMode,
+    ) -> Result<(), DaemonError> {
+        {
+            let mut guard = bridge.blocking_lock();
+            guard.spawn_framed(daemon_path, event_tx.clone(), framing)?;
+        }
+
+        let monitor_bridge = Arc::clone(&bridge);
+        std::thread::spawn(move || {
+            Self::supervise(monitor_bridge, event_tx);
+        });
+
+        Ok(())
+    }
+
+    /// Background loop that polls the child for liveness and respawns it
+    /// on an unexpected exit, backing off exponentially between attempts.
+    fn supervise(bridge: SharedDaemonBridge, event_tx: mpsc::UnboundedSender<DaemonEvent>) {
+        let mut attempt: u32 = 0;
+
+        loop {
+            std::thread::sleep(SUPERVISOR_POLL_INTERVAL);
+
+            let (daemon_path, exit_code) = {
+                let mut guard = bridge.blocking_lock();
+                if guard.intentional_shutdown.load(Ordering::SeqCst) {
+                    return;
+                }
+                if guard.is_running() {
+                    attempt = 0;
+                    continue;
+                }
+                match guard.daemon_path.clone() {
+                    Some(path) => (path, guard.last_exit_code),
+                    None => return,
+                }
+            };
+
+            if attempt >= SUPERVISOR_MAX_RESTARTS {
+                error!(
+                    "Daemon exceeded {} restart attempts, giving up",
+                    SUPERVISOR_MAX_RESTARTS
+                );
+                return;
+            }
+
+            {
+                let guard = bridge.blocking_lock();
+                let mut pending = guard.pending_responses.blocking_write();
+                for (_, sender) in pending.drain() {
+                    let _ = sender.send(Err(DaemonError::NotRunning));
+                }
+
+                // Any interactive stream/PTY session (`open_stream`) left
+                // open across the crash has no one left to answer it: the
+                // daemon process it was talking to is gone. Without this,
+                // the id stays orphaned in `pending_streams` forever and
+                // the frontend's StreamHandle/receiver just hangs, since
+                // nothing ever signals a terminal event. Synthesize the
+                // `stream-end` each one is waiting for instead.
+                let mut streams = guard.pending_streams.blocking_write();
+                for (id, sender) in streams.drain() {
+                    let _ = sender.send(DaemonEvent {
+                        msg_type: "evt".to_string(),
+                        evt: "stream-end".to_string(),
+                        id: Some(id),
+                        data: serde_json::json!({"reason": "daemon restarted"}),
+                    });
+                }
+            }
+
+            let backoff = supervisor_backoff(attempt);
+            attempt += 1;
+            warn!(
+                "Daemon exited unexpectedly (exit code {:?}), restarting in {:?} (attempt {})",
+                exit_code, backoff, attempt
+            );
+            std::thread::sleep(backoff);
+
+            let mut guard = bridge.blocking_lock();
+            if guard.intentional_shutdown.load(Ordering::SeqCst) {
+                return;
+            }
+            let framing = guard.framing;
+            match guard.spawn_framed(daemon_path, event_tx.clone(), framing) {
+                Ok(()) => {
+                    let _ = event_tx.send(DaemonEvent {
+                        msg_type: "evt".to_string(),
+                        evt: "daemon-restarted".to_string(),
+                        id: None,
+                        data: serde_json::json!({
+                            "attempt": attempt,
+                            "exit_code": exit_code,
+                        }),
+                    });
+                }
+                Err(e) => error!("Failed to respawn daemon: {}", e),
+            }
+        }
+    }
+
+    /// Insert into `pending_responses` from a synchronous context that may
+    /// or may not be running inside a Tokio runtime. `RwLock::blocking_write`
+    /// panics if the calling thread is already driving a runtime, which is
+    /// exactly the case when `spawn`/`spawn_framed`/`attach` are invoked
+    /// from an async Tauri command — so the lock is taken on a dedicated OS
+    /// thread instead, with completion signalled back over a plain
+    /// `std::sync::mpsc` channel.
+    fn register_pending_blocking(&self, id: String, tx: PendingResponse) {
+        let pending = Arc::clone(&self.pending_responses);
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            pending.blocking_write().insert(id, tx);
+            let _ = done_tx.send(());
+        });
+        let _ = done_rx.recv();
+    }
+
+    /// Counterpart to [`register_pending_blocking`] for removal.
+    fn remove_pending_blocking(&self, id: &str) {
+        let pending = Arc::clone(&self.pending_responses);
+        let id = id.to_string();
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            pending.blocking_write().remove(&id);
+            let _ = done_tx.send(());
+        });
+        let _ = done_rx.recv();
+    }
+
+    /// Send a `hello` command and block until the daemon replies with its
+    /// protocol version and supported command set, rejecting the spawn if
+    /// the major protocol versions don't match.
+    fn handshake(&mut self) -> Result<(), DaemonError> {
+        let hello = DaemonCommand::new(
+            "hello",
+            serde_json::json!({
+                "protocol_version": CLIENT_PROTOCOL_VERSION,
+                "client_version": env!("CARGO_PKG_VERSION"),
+            }),
+        );
+        let hello_id = hello.id.clone();
+
+        let (tx, rx) = oneshot::channel();
+        self.register_pending_blocking(hello_id.clone(), tx);
+
+        if let Err(e) = self.write_command(hello) {
+            self.remove_pending_blocking(&hello_id);
+            return Err(e);
+        }
+
+        // `rx.blocking_recv` blocks the current thread, so hop it onto a
+        // helper thread and race it against the handshake timeout. This is
+        // safe even when `handshake` is reached from async code: the
+        // spawned thread isn't one of the runtime's own worker threads, so
+        // it isn't "inside" the runtime for the purposes of Tokio's
+        // blocking-API panic check.
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = done_tx.send(rx.blocking_recv());
+        });
+
+        let event = match done_rx.recv_timeout(HANDSHAKE_TIMEOUT) {
+            Ok(Ok(result)) => result?,
+            Ok(Err(_)) => {
+                return Err(DaemonError::SendError(
+                    "handshake channel closed".to_string(),
+                ))
+            }
+            Err(_) => {
+                self.remove_pending_blocking(&hello_id);
+                return Err(DaemonError::Timeout);
+            }
+        };
+
+        if event.evt != "ready" && event.evt != "hello-ack" {
+            return Err(DaemonError::DaemonError(format!(
+                "unexpected handshake reply: {}",
+                event.evt
+            )));
+        }
+
+        let daemon_version = event
+            .data
+            .get("protocol_version")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0) as u32;
+        let capabilities: HashSet<String> = event
+            .data
+            .get("capabilities")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|c| c.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if daemon_version != CLIENT_PROTOCOL_VERSION {
+            return Err(DaemonError::ProtocolMismatch {
+                client: CLIENT_PROTOCOL_VERSION,
+                daemon: daemon_version,
+            });
+        }
+
+        info!(
+            "Handshake complete: protocol v{}, {} capabilities",
+            daemon_version,
+            capabilities.len()
+        );
+        self.protocol_version = Some(daemon_version);
+        self.capabilities = Some(capabilities);
         Ok(())
     }
 
+    /// Dispatch to the reader loop matching `framing`.
     fn read_stdout(
-        stdout: ChildStdout,
+        stdout: Box<dyn Read + Send>,
+        framing: FramingMode,
         pending: Arc<RwLock<HashMap<String, PendingResponse>>>,
+        streams: Arc<RwLock<HashMap<String, PendingStream>>>,
+        subscribers: Arc<RwLock<Vec<Subscriber>>>,
+        transfers: Arc<RwLock<HashMap<String, IncomingTransfer>>>,
+        event_tx: mpsc::UnboundedSender<DaemonEvent>,
+    ) {
+        match framing {
+            FramingMode::LineJson => {
+                Self::read_stdout_lines(stdout, pending, streams, subscribers, transfers, event_tx)
+            }
+            FramingMode::LengthPrefixed => Self::read_stdout_framed(
+                stdout, pending, streams, subscribers, transfers, event_tx,
+            ),
+        }
+    }
+
+    /// Read newline-delimited JSON messages from `stdout`.
+    fn read_stdout_lines(
+        stdout: Box<dyn Read + Send>,
+        pending: Arc<RwLock<HashMap<String, PendingResponse>>>,
+        streams: Arc<RwLock<HashMap<String, PendingStream>>>,
+        subscribers: Arc<RwLock<Vec<Subscriber>>>,
+        transfers: Arc<RwLock<HashMap<String, IncomingTransfer>>>,
         event_tx: mpsc::UnboundedSender<DaemonEvent>,
     ) {
         let reader = BufReader::new(stdout);
@@ -241,35 +916,15 @@ impl DaemonBridge {
 
                     debug!("Daemon stdout: {}", line);
 
-                    match serde_json::from_str::<DaemonEvent>(&line) {
-                        Ok(event) => {
-                            // Check if this is a response to a pending command
-                            if let Some(id) = &event.id {
-                                if !id.is_empty() {
-                                    // Use blocking approach for thread
-                                    let sender = {
-                                        if let Ok(mut pending_guard) = pending.try_write() {
-                                            pending_guard.remove(id)
-                                        } else {
-                                            None
-                                        }
-                                    };
-
-                                    if let Some(sender) = sender {
-                                        let _ = sender.send(Ok(event.clone()));
-                                    }
-                                }
-                            }
-
-                            // Always forward events to the event channel
-                            if event_tx.send(event).is_err() {
-                                warn!("Event channel closed");
-                                break;
-                            }
-                        }
-                        Err(e) => {
-                            warn!("Failed to parse daemon event: {} - line: {}", e, line);
-                        }
+                    if !Self::dispatch_event_bytes(
+                        line.as_bytes(),
+                        &pending,
+                        &streams,
+                        &subscribers,
+                        &transfers,
+                        &event_tx,
+                    ) {
+                        break;
                     }
                 }
                 Err(e) => {
@@ -282,6 +937,279 @@ impl DaemonBridge {
         info!("Daemon stdout reader exiting");
     }
 
+    /// Read 4-byte-big-endian-length-prefixed JSON messages from `stdout`,
+    /// rejecting any frame larger than [`MAX_FRAME_SIZE`].
+    fn read_stdout_framed(
+        stdout: Box<dyn Read + Send>,
+        pending: Arc<RwLock<HashMap<String, PendingResponse>>>,
+        streams: Arc<RwLock<HashMap<String, PendingStream>>>,
+        subscribers: Arc<RwLock<Vec<Subscriber>>>,
+        transfers: Arc<RwLock<HashMap<String, IncomingTransfer>>>,
+        event_tx: mpsc::UnboundedSender<DaemonEvent>,
+    ) {
+        let mut reader = BufReader::new(stdout);
+        let mut len_buf = [0u8; 4];
+
+        loop {
+            if let Err(e) = reader.read_exact(&mut len_buf) {
+                if e.kind() != std::io::ErrorKind::UnexpectedEof {
+                    error!("Error reading daemon frame length: {}", e);
+                }
+                break;
+            }
+
+            let len = u32::from_be_bytes(len_buf);
+            if len > MAX_FRAME_SIZE {
+                error!("Daemon frame of {} bytes exceeds max frame size", len);
+                break;
+            }
+
+            let mut payload = vec![0u8; len as usize];
+            if let Err(e) = reader.read_exact(&mut payload) {
+                error!("Error reading daemon frame payload: {}", e);
+                break;
+            }
+
+            if !Self::dispatch_event_bytes(
+                &payload,
+                &pending,
+                &streams,
+                &subscribers,
+                &transfers,
+                &event_tx,
+            ) {
+                break;
+            }
+        }
+
+        info!("Daemon stdout reader exiting");
+    }
+
+    /// Parse one message's worth of bytes as a [`DaemonEvent`], route it to
+    /// any pending response/stream/subscriber, and forward it on
+    /// `event_tx`. Returns `false` when the caller's read loop should stop
+    /// (the event channel has closed).
+    fn dispatch_event_bytes(
+        data: &[u8],
+        pending: &Arc<RwLock<HashMap<String, PendingResponse>>>,
+        streams: &Arc<RwLock<HashMap<String, PendingStream>>>,
+        subscribers: &Arc<RwLock<Vec<Subscriber>>>,
+        transfers: &Arc<RwLock<HashMap<String, IncomingTransfer>>>,
+        event_tx: &mpsc::UnboundedSender<DaemonEvent>,
+    ) -> bool {
+        let event = match serde_json::from_slice::<DaemonEvent>(data) {
+            Ok(event) => event,
+            Err(e) => {
+                warn!("Failed to parse daemon event: {}", e);
+                return true;
+            }
+        };
+
+        // Incoming file chunks are reassembled to disk here rather than
+        // forwarded raw: a large base64 payload per chunk would otherwise
+        // flood every subscriber and the Tauri event bus. Synthesized
+        // progress/completion events take their place.
+        if event.evt == "file_chunk" {
+            for synthesized in Self::handle_incoming_chunk(transfers, &event) {
+                Self::broadcast_event(subscribers, &synthesized);
+                if event_tx.send(synthesized).is_err() {
+                    warn!("Event channel closed");
+                    return false;
+                }
+            }
+            return true;
+        }
+
+        // Check if this is a response to a pending command
+        let mut was_response = false;
+        if let Some(id) = &event.id {
+            if !id.is_empty() {
+                // Use blocking approach for thread
+                let sender = {
+                    if let Ok(mut pending_guard) = pending.try_write() {
+                        pending_guard.remove(id)
+                    } else {
+                        None
+                    }
+                };
+
+                if let Some(sender) = sender {
+                    was_response = true;
+                    let result = match event.evt.as_str() {
+                        "cancelled" => Err(DaemonError::Cancelled),
+                        "denied" | "rejected" => Err(DaemonError::PeerRejected(
+                            event
+                                .data
+                                .get("reason")
+                                .and_then(|v| v.as_str())
+                                .unwrap_or("request denied by peer")
+                                .to_string(),
+                        )),
+                        "error" => Err(DaemonError::DaemonError(
+                            event
+                                .data
+                                .get("message")
+                                .and_then(|v| v.as_str())
+                                .unwrap_or("daemon reported an error")
+                                .to_string(),
+                        )),
+                        _ => Ok(event.clone()),
+                    };
+                    let _ = sender.send(result);
+                } else {
+                    // Not a one-shot response; see if it belongs
+                    // to an open streaming session instead.
+                    let is_end = event.evt == "stream-end";
+                    let stream_sender = if let Ok(mut streams_guard) = streams.try_write() {
+                        if is_end {
+                            streams_guard.remove(id)
+                        } else {
+                            streams_guard.get(id).cloned()
+                        }
+                    } else {
+                        None
+                    };
+
+                    if let Some(stream_sender) = stream_sender {
+                        let _ = stream_sender.send(event.clone());
+                    }
+                }
+            }
+        }
+
+        // Fan non-response events out to every subscriber whose filter
+        // (if any) accepts this `evt`.
+        if !was_response {
+            Self::broadcast_event(subscribers, &event);
+        }
+
+        // Always forward events to the event channel
+        if event_tx.send(event).is_err() {
+            warn!("Event channel closed");
+            return false;
+        }
+
+        true
+    }
+
+    /// Send `event` to every subscriber whose filter accepts it, dropping
+    /// any subscriber whose receiver has gone away.
+    fn broadcast_event(subscribers: &Arc<RwLock<Vec<Subscriber>>>, event: &DaemonEvent) {
+        let Ok(mut subs) = subscribers.try_write() else {
+            return;
+        };
+        subs.retain(|(filter, tx)| {
+            if filter.as_ref().is_none_or(|names| names.contains(&event.evt)) {
+                tx.send(event.clone()).is_ok() || tx.receiver_count() > 0
+            } else {
+                tx.receiver_count() > 0
+            }
+        });
+    }
+
+    /// Append one incoming `file_chunk` event's payload to the transfer it
+    /// belongs to, creating the destination file on the first chunk seen
+    /// for that id. Returns the `transfer_progress` event for this chunk,
+    /// plus a trailing `transfer_complete` event (and removes the transfer
+    /// from `transfers`) once every chunk has arrived.
+    fn handle_incoming_chunk(
+        transfers: &Arc<RwLock<HashMap<String, IncomingTransfer>>>,
+        event: &DaemonEvent,
+    ) -> Vec<DaemonEvent> {
+        let id = match event.data.get("id").and_then(|v| v.as_str()) {
+            Some(id) => id.to_string(),
+            None => {
+                warn!("file_chunk event missing id");
+                return Vec::new();
+            }
+        };
+        // `id` is attacker-controlled: it comes straight off the wire from
+        // the remote peer's side of the transfer and is used below to build
+        // the destination path. Reject anything that isn't the uuid format
+        // the sending side actually generates (see `send_file`) before it
+        // ever reaches the filesystem, so a `..`/`/`-laced id can't write
+        // outside the temp directory.
+        if uuid::Uuid::parse_str(&id).is_err() {
+            warn!("file_chunk event has non-uuid id {:?}, dropping", id);
+            return Vec::new();
+        }
+        let total_chunks = event.data.get("total").and_then(|v| v.as_u64()).unwrap_or(0);
+        let data = match event
+            .data
+            .get("data")
+            .and_then(|v| v.as_str())
+            .map(|s| base64::Engine::decode(&base64::engine::general_purpose::STANDARD, s))
+        {
+            Some(Ok(bytes)) => bytes,
+            _ => {
+                warn!("file_chunk event for {} has invalid data", id);
+                return Vec::new();
+            }
+        };
+
+        let Ok(mut guard) = transfers.try_write() else {
+            warn!("transfers map busy, dropping chunk for {}", id);
+            return Vec::new();
+        };
+
+        if !guard.contains_key(&id) {
+            let dest_path = std::env::temp_dir().join(format!("kamune-transfer-{}", id));
+            let file = match std::fs::File::create(&dest_path) {
+                Ok(file) => file,
+                Err(e) => {
+                    warn!("failed to create transfer file for {}: {}", id, e);
+                    return Vec::new();
+                }
+            };
+            guard.insert(
+                id.clone(),
+                IncomingTransfer {
+                    file,
+                    dest_path,
+                    received_bytes: 0,
+                    received_chunks: 0,
+                    total_chunks,
+                },
+            );
+        }
+
+        let transfer = guard.get_mut(&id).expect("just inserted if absent");
+        if let Err(e) = transfer.file.write_all(&data) {
+            warn!("failed to write transfer chunk for {}: {}", id, e);
+            return Vec::new();
+        }
+        transfer.received_bytes += data.len() as u64;
+        transfer.received_chunks += 1;
+
+        let mut events = vec![DaemonEvent {
+            msg_type: "evt".to_string(),
+            evt: "transfer_progress".to_string(),
+            id: None,
+            data: serde_json::json!({
+                "id": id,
+                "received_chunks": transfer.received_chunks,
+                "total_chunks": transfer.total_chunks,
+                "received_bytes": transfer.received_bytes,
+            }),
+        }];
+
+        if transfer.received_chunks >= transfer.total_chunks {
+            let dest_path = transfer.dest_path.clone();
+            guard.remove(&id);
+            events.push(DaemonEvent {
+                msg_type: "evt".to_string(),
+                evt: "transfer_complete".to_string(),
+                id: None,
+                data: serde_json::json!({
+                    "id": id,
+                    "path": dest_path,
+                }),
+            });
+        }
+
+        events
+    }
+
     fn read_stderr(stderr: std::process::ChildStderr) {
         let reader = BufReader::new(stderr);
 
@@ -302,26 +1230,64 @@ impl DaemonBridge {
         info!("Daemon stderr reader exiting");
     }
 
-    /// Send a command to the daemon
+    /// Send a command to the daemon, rejecting it locally if the daemon's
+    /// negotiated capabilities don't include it.
     pub fn send_command(&mut self, command: DaemonCommand) -> Result<String, DaemonError> {
-        let stdin = self.stdin.as_mut().ok_or(DaemonError::NotRunning)?;
+        self.check_capability(&command.cmd)?;
+        self.write_command(command)
+    }
 
+    /// Write a command to the daemon's stdin without any capability
+    /// gating. Used internally for the handshake itself, which must be
+    /// sent before any capabilities are known.
+    fn write_command(&mut self, command: DaemonCommand) -> Result<String, DaemonError> {
         let json = serde_json::to_string(&command)?;
         debug!("Sending command: {}", json);
 
-        writeln!(stdin, "{}", json)
-            .map_err(|e| DaemonError::SendError(format!("failed to write to stdin: {}", e)))?;
+        self.write_framed(json.as_bytes())?;
+        Ok(command.id)
+    }
+
+    /// Write a single message to the daemon's stdin using the bridge's
+    /// negotiated [`FramingMode`].
+    fn write_framed(&mut self, payload: &[u8]) -> Result<(), DaemonError> {
+        if payload.len() as u64 > MAX_FRAME_SIZE as u64 {
+            return Err(DaemonError::FrameTooLarge(payload.len() as u32));
+        }
+
+        let framing = self.framing;
+        let stdin = self.stdin.as_mut().ok_or(DaemonError::NotRunning)?;
+
+        match framing {
+            FramingMode::LineJson => {
+                writeln!(stdin, "{}", String::from_utf8_lossy(payload)).map_err(|e| {
+                    DaemonError::SendError(format!("failed to write to stdin: {}", e))
+                })?;
+            }
+            FramingMode::LengthPrefixed => {
+                let len = payload.len() as u32;
+                stdin.write_all(&len.to_be_bytes()).map_err(|e| {
+                    DaemonError::SendError(format!("failed to write frame length: {}", e))
+                })?;
+                stdin
+                    .write_all(payload)
+                    .map_err(|e| DaemonError::SendError(format!("failed to write frame: {}", e)))?;
+            }
+        }
+
         stdin
             .flush()
             .map_err(|e| DaemonError::SendError(format!("failed to flush stdin: {}", e)))?;
 
-        Ok(command.id)
+        Ok(())
     }
 
-    /// Send a command and wait for a response
+    /// Send a command and wait for a response. `timeout` bounds how long
+    /// to wait; pass `None` to wait indefinitely.
     pub async fn send_command_async(
         bridge: Arc<Mutex<DaemonBridge>>,
         command: DaemonCommand,
+        timeout: Option<Duration>,
     ) -> Result<DaemonEvent, DaemonError> {
         let (tx, rx) = oneshot::channel();
         let cmd_id = command.id.clone();
@@ -339,26 +1305,221 @@ impl DaemonBridge {
             bridge_guard.send_command(command)?;
         }
 
-        // Wait for response with timeout
-        match tokio::time::timeout(std::time::Duration::from_secs(30), rx).await {
-            Ok(Ok(result)) => result,
-            Ok(Err(_)) => Err(DaemonError::SendError("response channel closed".to_string())),
-            Err(_) => {
-                // Remove from pending on timeout
-                let bridge_guard = bridge.lock().await;
-                let mut pending = bridge_guard.pending_responses.write().await;
-                pending.remove(&cmd_id);
-                Err(DaemonError::Timeout)
+        let recv = async {
+            match rx.await {
+                Ok(result) => result,
+                Err(_) => Err(DaemonError::SendError("response channel closed".to_string())),
             }
+        };
+
+        match timeout {
+            None => recv.await,
+            Some(duration) => match tokio::time::timeout(duration, recv).await {
+                Ok(result) => result,
+                Err(_) => {
+                    // Remove from pending on timeout
+                    let bridge_guard = bridge.lock().await;
+                    let mut pending = bridge_guard.pending_responses.write().await;
+                    pending.remove(&cmd_id);
+                    Err(DaemonError::Timeout)
+                }
+            },
         }
     }
 
+    /// Cancel an in-flight command: asks the daemon to stop processing
+    /// `id`. The pending response is left registered so that once the
+    /// daemon confirms with a `cancelled` event, `send_command_async`
+    /// resolves immediately with [`DaemonError::Cancelled`] instead of
+    /// waiting out its timeout.
+    pub async fn cancel_command(bridge: SharedDaemonBridge, id: &str) -> Result<(), DaemonError> {
+        let mut guard = bridge.lock().await;
+        guard.write_raw_frame(&serde_json::json!({
+            "type": "cancel",
+            "id": id,
+        }))
+    }
+
+    /// Send the file at `path` to the daemon for `session_id`, split into
+    /// [`FILE_CHUNK_SIZE`] chunks framed as `file_chunk` messages rather
+    /// than one single base64 blob, so the daemon can start forwarding
+    /// bytes before the whole file is read. Emits `transfer_progress`
+    /// events as chunks go out and a final `transfer_complete` event, on
+    /// the bridge's event channel, the same way the supervisor synthesizes
+    /// `daemon-restarted`. Returns the generated transfer id.
+    pub async fn send_file(
+        bridge: SharedDaemonBridge,
+        session_id: &str,
+        path: &Path,
+    ) -> Result<String, DaemonError> {
+        let transfer_id = uuid::Uuid::new_v4().to_string();
+        let metadata = std::fs::metadata(path)
+            .map_err(|e| DaemonError::Transfer(format!("failed to stat {:?}: {}", path, e)))?;
+        let total_bytes = metadata.len();
+        // A zero-byte file still needs exactly one (empty) chunk sent, so
+        // the peer creates its `IncomingTransfer`/destination file instead
+        // of silently receiving nothing; see the `seq`/`n` handling below.
+        let total_chunks = total_bytes.div_ceil(FILE_CHUNK_SIZE as u64).max(1);
+
+        let mut file = std::fs::File::open(path)
+            .map_err(|e| DaemonError::Transfer(format!("failed to open {:?}: {}", path, e)))?;
+
+        let event_tx = bridge
+            .lock()
+            .await
+            .event_tx
+            .clone()
+            .ok_or(DaemonError::NotRunning)?;
+
+        let mut buf = vec![0u8; FILE_CHUNK_SIZE];
+        let mut seq = 0u64;
+        let mut sent_bytes = 0u64;
+        loop {
+            let n = file
+                .read(&mut buf)
+                .map_err(|e| DaemonError::Transfer(format!("failed to read {:?}: {}", path, e)))?;
+            // Every real file needs at least its first read to decide
+            // there's anything to send; only treat EOF as "done" once a
+            // chunk has actually gone out, so a zero-byte file still gets
+            // its one (empty) chunk instead of the loop exiting before
+            // ever sending one.
+            if n == 0 && seq > 0 {
+                break;
+            }
+
+            let encoded = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &buf[..n]);
+            {
+                let mut guard = bridge.lock().await;
+                guard.write_raw_frame(&serde_json::json!({
+                    "type": "file_chunk",
+                    "id": transfer_id,
+                    "session_id": session_id,
+                    "seq": seq,
+                    "total": total_chunks,
+                    "size": n,
+                    "data": encoded,
+                }))?;
+            }
+
+            seq += 1;
+            sent_bytes += n as u64;
+            let _ = event_tx.send(DaemonEvent {
+                msg_type: "evt".to_string(),
+                evt: "transfer_progress".to_string(),
+                id: None,
+                data: serde_json::json!({
+                    "id": transfer_id,
+                    "sent_chunks": seq,
+                    "total_chunks": total_chunks,
+                    "sent_bytes": sent_bytes,
+                }),
+            });
+
+            if n == 0 {
+                break;
+            }
+        }
+
+        let _ = event_tx.send(DaemonEvent {
+            msg_type: "evt".to_string(),
+            evt: "transfer_complete".to_string(),
+            id: None,
+            data: serde_json::json!({
+                "id": transfer_id,
+                "sent_bytes": sent_bytes,
+            }),
+        });
+
+        Ok(transfer_id)
+    }
+
+    /// Subscribe to daemon events, optionally restricted to a set of `evt`
+    /// names. Multiple independent subscribers may observe the same event
+    /// stream; each gets its own `broadcast::Receiver` fed from the
+    /// daemon's stdout reader.
+    pub async fn subscribe(&self, filter: Option<Vec<String>>) -> broadcast::Receiver<DaemonEvent> {
+        let (tx, rx) = broadcast::channel(SUBSCRIBER_CHANNEL_CAPACITY);
+        let filter = filter.map(|names| names.into_iter().collect::<HashSet<_>>());
+        self.subscribers.write().await.push((filter, tx));
+        rx
+    }
+
+    /// Open a bidirectional streaming command session, e.g. an interactive
+    /// shell or PTY. Unlike [`send_command_async`](Self::send_command_async),
+    /// events carrying the command's id are not treated as a single
+    /// request/response pair: they are forwarded on the returned receiver
+    /// until a terminal `stream-end` event arrives.
+    pub async fn open_stream(
+        bridge: SharedDaemonBridge,
+        cmd: &str,
+        params: serde_json::Value,
+    ) -> Result<(StreamHandle, mpsc::UnboundedReceiver<DaemonEvent>), DaemonError> {
+        let command = DaemonCommand::new(cmd, params);
+        let id = command.id.clone();
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        {
+            let mut guard = bridge.lock().await;
+            guard.check_capability(&command.cmd)?;
+            guard.pending_streams.write().await.insert(id.clone(), tx);
+            if let Err(e) = guard.write_command(command) {
+                guard.pending_streams.write().await.remove(&id);
+                return Err(e);
+            }
+        }
+
+        Ok((
+            StreamHandle {
+                id,
+                bridge: Arc::clone(&bridge),
+            },
+            rx,
+        ))
+    }
+
+    /// Frame `data` as a stream message tagged with `id` and write it to
+    /// the daemon's stdin.
+    fn write_stream_frame(&mut self, id: &str, data: &[u8]) -> Result<(), DaemonError> {
+        let encoded = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, data);
+        self.write_raw_frame(&serde_json::json!({
+            "type": "stream",
+            "id": id,
+            "data": encoded,
+        }))
+    }
+
+    /// Write a control message (e.g. `cancel`) for the stream `id`.
+    fn write_stream_control(&mut self, id: &str, action: &str) -> Result<(), DaemonError> {
+        self.write_raw_frame(&serde_json::json!({
+            "type": action,
+            "id": id,
+        }))
+    }
+
+    /// Write a PTY resize control message for the stream `id`.
+    fn write_stream_resize(&mut self, id: &str, cols: u16, rows: u16) -> Result<(), DaemonError> {
+        self.write_raw_frame(&serde_json::json!({
+            "type": "resize",
+            "id": id,
+            "cols": cols,
+            "rows": rows,
+        }))
+    }
+
+    /// Serialize and write an arbitrary JSON frame to the daemon's stdin.
+    fn write_raw_frame(&mut self, frame: &serde_json::Value) -> Result<(), DaemonError> {
+        let json = serde_json::to_string(frame)?;
+        debug!("Sending frame: {}", json);
+        self.write_framed(json.as_bytes())
+    }
+
     /// Check if the daemon is running
     pub fn is_running(&mut self) -> bool {
         if let Some(ref mut child) = self.child {
             match child.try_wait() {
-                Ok(Some(_)) => {
+                Ok(Some(status)) => {
                     // Process has exited
+                    self.last_exit_code = status.code();
                     self.child = None;
                     self.stdin = None;
                     false
@@ -367,25 +1528,33 @@ impl DaemonBridge {
                 Err(_) => false,
             }
         } else {
-            false
+            // Attached bridges have no owned `Child` to poll; a connected
+            // stdin is the only liveness signal we have until a write
+            // fails.
+            !self.owns_process && self.stdin.is_some()
         }
     }
 
-    /// Stop the daemon
+    /// Stop the daemon, or disconnect from it if it was reached via
+    /// [`attach`](Self::attach) rather than owned by this bridge.
     pub fn stop(&mut self) -> Result<(), DaemonError> {
-        // Send shutdown command first
-        if self.stdin.is_some() {
-            let shutdown_cmd = DaemonCommand::new("shutdown", serde_json::json!({}));
-            let _ = self.send_command(shutdown_cmd);
-        }
+        self.intentional_shutdown.store(true, Ordering::SeqCst);
+
+        if self.owns_process {
+            // Send shutdown command first
+            if self.stdin.is_some() {
+                let shutdown_cmd = DaemonCommand::new("shutdown", serde_json::json!({}));
+                let _ = self.send_command(shutdown_cmd);
+            }
 
-        // Give the daemon a moment to shut down gracefully
-        std::thread::sleep(std::time::Duration::from_millis(500));
+            // Give the daemon a moment to shut down gracefully
+            std::thread::sleep(std::time::Duration::from_millis(500));
 
-        // Force kill if still running
-        if let Some(ref mut child) = self.child {
-            let _ = child.kill();
-            let _ = child.wait();
+            // Force kill if still running
+            if let Some(ref mut child) = self.child {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
         }
 
         // Clean up
@@ -393,7 +1562,10 @@ impl DaemonBridge {
         self.stdin = None;
         self.shutdown_tx = None;
 
-        info!("Daemon stopped");
+        info!(
+            "Daemon {}",
+            if self.owns_process { "stopped" } else { "detached" }
+        );
         Ok(())
     }
 }
@@ -437,4 +1609,226 @@ mod tests {
         assert_eq!(event.msg_type, "evt");
         assert_eq!(event.evt, "ready");
     }
+
+    #[test]
+    fn test_supports_and_check_capability_before_handshake() {
+        // No handshake has completed yet, so there's no capability
+        // information to gate on: everything is reported supported.
+        let bridge = DaemonBridge::new();
+        assert!(bridge.supports("dial"));
+        assert!(bridge.check_capability("dial").is_ok());
+    }
+
+    #[test]
+    fn test_check_capability_rejects_unadvertised_command() {
+        let mut bridge = DaemonBridge::new();
+        bridge.capabilities = Some(["dial".to_string()].into_iter().collect());
+
+        assert!(bridge.supports("dial"));
+        assert!(!bridge.supports("spawn_process"));
+
+        match bridge.check_capability("spawn_process") {
+            Err(DaemonError::Unsupported(cmd)) => assert_eq!(cmd, "spawn_process"),
+            other => panic!("expected Unsupported, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_control_endpoint_parse_tcp() {
+        let endpoint = ControlEndpoint::parse("127.0.0.1:4242").unwrap();
+        assert_eq!(
+            endpoint,
+            ControlEndpoint::Tcp("127.0.0.1:4242".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_control_endpoint_parse_unix_path() {
+        let endpoint = ControlEndpoint::parse("/tmp/kamune.sock").unwrap();
+        assert_eq!(endpoint, ControlEndpoint::Unix(PathBuf::from("/tmp/kamune.sock")));
+    }
+
+    #[test]
+    fn test_control_endpoint_parse_rejects_empty() {
+        assert!(ControlEndpoint::parse("").is_err());
+    }
+
+    #[test]
+    fn test_supervisor_backoff_doubles_and_caps() {
+        assert_eq!(supervisor_backoff(0), SUPERVISOR_BASE_BACKOFF);
+        assert_eq!(supervisor_backoff(1), SUPERVISOR_BASE_BACKOFF * 2);
+        assert_eq!(supervisor_backoff(2), SUPERVISOR_BASE_BACKOFF * 4);
+        assert_eq!(supervisor_backoff(10), SUPERVISOR_MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_write_framed_rejects_oversized_payload() {
+        let mut bridge = DaemonBridge::new();
+        bridge.framing = FramingMode::LengthPrefixed;
+        let payload = vec![0u8; MAX_FRAME_SIZE as usize + 1];
+        match bridge.write_framed(&payload) {
+            Err(DaemonError::FrameTooLarge(len)) => assert_eq!(len, payload.len() as u32),
+            other => panic!("expected FrameTooLarge, got {:?}", other),
+        }
+    }
+
+    /// `Write` sink that captures everything written to it, standing in for
+    /// the daemon's stdin in framing round-trip tests.
+    struct VecSink(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl Write for VecSink {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_length_prefixed_round_trip() {
+        let captured = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut bridge = DaemonBridge::new();
+        bridge.framing = FramingMode::LengthPrefixed;
+        bridge.stdin = Some(Box::new(VecSink(Arc::clone(&captured))));
+
+        bridge
+            .write_raw_frame(&serde_json::json!({
+                "type": "evt",
+                "evt": "ready",
+                "data": {"ok": true},
+            }))
+            .unwrap();
+
+        let framed = captured.lock().unwrap().clone();
+
+        let pending = Arc::new(RwLock::new(HashMap::new()));
+        let streams = Arc::new(RwLock::new(HashMap::new()));
+        let subscribers = Arc::new(RwLock::new(Vec::new()));
+        let transfers = Arc::new(RwLock::new(HashMap::new()));
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+
+        DaemonBridge::read_stdout_framed(
+            Box::new(std::io::Cursor::new(framed)),
+            pending,
+            streams,
+            subscribers,
+            transfers,
+            event_tx,
+        );
+
+        let received = event_rx.try_recv().expect("expected one decoded event");
+        assert_eq!(received.evt, "ready");
+    }
+
+    #[test]
+    fn test_read_stdout_framed_rejects_oversized_frame() {
+        let mut framed = Vec::new();
+        framed.extend_from_slice(&(MAX_FRAME_SIZE + 1).to_be_bytes());
+
+        let pending = Arc::new(RwLock::new(HashMap::new()));
+        let streams = Arc::new(RwLock::new(HashMap::new()));
+        let subscribers = Arc::new(RwLock::new(Vec::new()));
+        let transfers = Arc::new(RwLock::new(HashMap::new()));
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+
+        DaemonBridge::read_stdout_framed(
+            Box::new(std::io::Cursor::new(framed)),
+            pending,
+            streams,
+            subscribers,
+            transfers,
+            event_tx,
+        );
+
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    fn file_chunk_event(id: &str, seq: u64, total: u64, data: &[u8]) -> DaemonEvent {
+        DaemonEvent {
+            msg_type: "evt".to_string(),
+            evt: "file_chunk".to_string(),
+            id: None,
+            data: serde_json::json!({
+                "id": id,
+                "seq": seq,
+                "total": total,
+                "data": base64::Engine::encode(&base64::engine::general_purpose::STANDARD, data),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_handle_incoming_chunk_reassembles_and_completes() {
+        let transfers = Arc::new(RwLock::new(HashMap::new()));
+        let id = uuid::Uuid::new_v4().to_string();
+
+        let first =
+            DaemonBridge::handle_incoming_chunk(&transfers, &file_chunk_event(&id, 0, 2, b"hello "));
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].evt, "transfer_progress");
+        assert_eq!(
+            first[0].data.get("received_chunks").and_then(|v| v.as_u64()),
+            Some(1)
+        );
+
+        let second =
+            DaemonBridge::handle_incoming_chunk(&transfers, &file_chunk_event(&id, 1, 2, b"world"));
+        assert_eq!(second.len(), 2);
+        assert_eq!(second[0].evt, "transfer_progress");
+        assert_eq!(second[1].evt, "transfer_complete");
+
+        assert!(transfers.try_read().unwrap().is_empty());
+
+        let dest_path = second[1]
+            .data
+            .get("path")
+            .and_then(|v| v.as_str())
+            .map(PathBuf::from)
+            .expect("transfer_complete carries the destination path");
+        assert_eq!(
+            std::fs::read_to_string(&dest_path).unwrap(),
+            "hello world"
+        );
+        let _ = std::fs::remove_file(&dest_path);
+    }
+
+    #[test]
+    fn test_broadcast_event_respects_filter() {
+        let (matching_tx, mut matching_rx) = broadcast::channel(8);
+        let (other_tx, mut other_rx) = broadcast::channel(8);
+        let (unfiltered_tx, mut unfiltered_rx) = broadcast::channel(8);
+
+        let subscribers: Arc<RwLock<Vec<Subscriber>>> = Arc::new(RwLock::new(vec![
+            (Some(["ready".to_string()].into_iter().collect()), matching_tx),
+            (Some(["other".to_string()].into_iter().collect()), other_tx),
+            (None, unfiltered_tx),
+        ]));
+
+        let event = DaemonEvent {
+            msg_type: "evt".to_string(),
+            evt: "ready".to_string(),
+            id: None,
+            data: serde_json::json!({}),
+        };
+
+        DaemonBridge::broadcast_event(&subscribers, &event);
+
+        assert_eq!(matching_rx.try_recv().unwrap().evt, "ready");
+        assert!(other_rx.try_recv().is_err());
+        assert_eq!(unfiltered_rx.try_recv().unwrap().evt, "ready");
+    }
+
+    #[test]
+    fn test_handle_incoming_chunk_rejects_non_uuid_id() {
+        let transfers = Arc::new(RwLock::new(HashMap::new()));
+        let events = DaemonBridge::handle_incoming_chunk(
+            &transfers,
+            &file_chunk_event("../../../etc/passwd", 0, 1, b"x"),
+        );
+        assert!(events.is_empty());
+        assert!(transfers.try_read().unwrap().is_empty());
+    }
 }
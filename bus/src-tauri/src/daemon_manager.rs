@@ -0,0 +1,317 @@
+//! Daemon Manager Module
+//!
+//! Generalizes `SharedDaemonBridge` into a manager that can juggle several
+//! daemon connections at once, each addressed by a stable [`DaemonId`], and
+//! multiplexes their events onto one aggregated, connection-tagged stream.
+//! A connection is either `launch`ed (a freshly spawned, locally supervised
+//! process) or `connect`ed (attached to an already-running daemon at a
+//! [`ControlEndpoint`]).
+
+use crate::daemon_bridge::{
+    create_shared_bridge, ControlEndpoint, DaemonBridge, DaemonCommand, DaemonError, DaemonEvent,
+    FramingMode, SharedDaemonBridge, StreamHandle, DEFAULT_COMMAND_TIMEOUT,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc, RwLock};
+
+/// Stable identifier for a daemon connection managed by [`DaemonManager`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct DaemonId(String);
+
+impl DaemonId {
+    fn new() -> Self {
+        Self(uuid::Uuid::new_v4().to_string())
+    }
+
+    /// Wrap an id previously handed out by this manager, e.g. one supplied
+    /// by a Tauri command argument.
+    pub fn from_string(id: String) -> Self {
+        Self(id)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for DaemonId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// How a managed daemon connection was established, kept around for
+/// display in [`DaemonStatus`].
+#[derive(Debug, Clone)]
+enum DaemonSource {
+    /// Spawned and supervised locally from a binary path.
+    Launched(PathBuf),
+    /// Attached to an already-running daemon at a control endpoint.
+    Connected(ControlEndpoint),
+}
+
+impl std::fmt::Display for DaemonSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Launched(path) => write!(f, "{}", path.display()),
+            Self::Connected(endpoint) => write!(f, "{}", endpoint),
+        }
+    }
+}
+
+/// A snapshot of one managed daemon's status.
+#[derive(Debug, Clone, Serialize)]
+pub struct DaemonStatus {
+    pub id: DaemonId,
+    pub source: String,
+    pub running: bool,
+}
+
+/// An event forwarded from a managed daemon, tagged with the connection
+/// it originated from.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaggedDaemonEvent {
+    pub daemon_id: DaemonId,
+    pub event: DaemonEvent,
+}
+
+struct ManagedDaemon {
+    bridge: SharedDaemonBridge,
+    source: DaemonSource,
+}
+
+/// Juggles several daemon connections at once, each addressed by a stable
+/// `DaemonId`, behind one client-facing API.
+#[derive(Clone)]
+pub struct DaemonManager {
+    daemons: Arc<RwLock<HashMap<DaemonId, ManagedDaemon>>>,
+    aggregated_tx: mpsc::UnboundedSender<TaggedDaemonEvent>,
+}
+
+impl DaemonManager {
+    /// Create a new manager along with the receiver for its aggregated
+    /// event stream.
+    pub fn new() -> (Self, mpsc::UnboundedReceiver<TaggedDaemonEvent>) {
+        let (aggregated_tx, aggregated_rx) = mpsc::unbounded_channel();
+        (
+            Self {
+                daemons: Arc::new(RwLock::new(HashMap::new())),
+                aggregated_tx,
+            },
+            aggregated_rx,
+        )
+    }
+
+    /// Spawn a new supervised daemon from `daemon_path` using `framing` as
+    /// its wire transport, returning the `DaemonId` it's addressed by.
+    pub async fn launch(
+        &self,
+        daemon_path: PathBuf,
+        framing: FramingMode,
+    ) -> Result<DaemonId, DaemonError> {
+        let id = DaemonId::new();
+        let bridge = create_shared_bridge();
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+
+        // `spawn_supervised_framed` blocks on a `tokio::sync::Mutex` and on
+        // the handshake's response wait, neither of which may be called
+        // directly from a thread that's already driving this runtime (as
+        // is the case here, since `launch` is awaited from an async Tauri
+        // command). Run it on a blocking-pool thread instead.
+        let spawn_bridge = Arc::clone(&bridge);
+        let spawn_path = daemon_path.clone();
+        tokio::task::spawn_blocking(move || {
+            DaemonBridge::spawn_supervised_framed(spawn_bridge, spawn_path, event_tx, framing)
+        })
+        .await
+        .map_err(|e| DaemonError::SendError(format!("spawn task panicked: {}", e)))??;
+
+        self.forward_tagged(id.clone(), event_rx);
+        self.daemons.write().await.insert(
+            id.clone(),
+            ManagedDaemon {
+                bridge,
+                source: DaemonSource::Launched(daemon_path),
+            },
+        );
+
+        Ok(id)
+    }
+
+    /// Attach to a daemon already running at `endpoint`, returning the
+    /// `DaemonId` it's addressed by.
+    pub async fn connect(
+        &self,
+        endpoint: ControlEndpoint,
+        framing: FramingMode,
+    ) -> Result<DaemonId, DaemonError> {
+        let id = DaemonId::new();
+        let bridge = create_shared_bridge();
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+
+        // `attach` blocks on the transport-level connect and on
+        // handshake()'s blocking wait, neither of which may run directly on
+        // a thread that's already driving this runtime (as is the case
+        // here, since `connect` is awaited from an async Tauri command).
+        // Run it on a blocking-pool thread instead, the same way `launch`
+        // does for `spawn_supervised`.
+        let attach_bridge = Arc::clone(&bridge);
+        let attach_endpoint = endpoint.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut guard = attach_bridge.blocking_lock();
+            guard.attach(&attach_endpoint, event_tx, framing)
+        })
+        .await
+        .map_err(|e| DaemonError::SendError(format!("attach task panicked: {}", e)))??;
+
+        self.forward_tagged(id.clone(), event_rx);
+        self.daemons.write().await.insert(
+            id.clone(),
+            ManagedDaemon {
+                bridge,
+                source: DaemonSource::Connected(endpoint),
+            },
+        );
+
+        Ok(id)
+    }
+
+    /// Tag and re-forward every event from a newly added connection onto
+    /// the manager's aggregated stream.
+    fn forward_tagged(&self, id: DaemonId, mut event_rx: mpsc::UnboundedReceiver<DaemonEvent>) {
+        let aggregated_tx = self.aggregated_tx.clone();
+        tokio::spawn(async move {
+            while let Some(event) = event_rx.recv().await {
+                let tagged = TaggedDaemonEvent {
+                    daemon_id: id.clone(),
+                    event,
+                };
+                if aggregated_tx.send(tagged).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Send a command to the daemon addressed by `id` and wait for its
+    /// response, timing out after `timeout` (or [`DEFAULT_COMMAND_TIMEOUT`]
+    /// if `None`).
+    pub async fn send_to(
+        &self,
+        id: &DaemonId,
+        command: DaemonCommand,
+        timeout: Option<Duration>,
+    ) -> Result<DaemonEvent, DaemonError> {
+        let bridge = self.bridge_for(id).await?;
+        DaemonBridge::send_command_async(bridge, command, Some(timeout.unwrap_or(DEFAULT_COMMAND_TIMEOUT)))
+            .await
+    }
+
+    /// Cancel an in-flight command previously sent to the daemon addressed
+    /// by `id`.
+    pub async fn cancel(&self, id: &DaemonId, command_id: &str) -> Result<(), DaemonError> {
+        let bridge = self.bridge_for(id).await?;
+        DaemonBridge::cancel_command(bridge, command_id).await
+    }
+
+    /// Open a bidirectional streaming command session (e.g. a remote
+    /// process or PTY) on the daemon addressed by `id`. The returned
+    /// receiver carries every event tagged with the stream's id until a
+    /// terminal `stream-end` arrives; callers that only need to notice
+    /// when the stream ends can drain and discard it; the live output
+    /// still reaches the frontend through the manager's aggregated event
+    /// stream like any other daemon event.
+    pub async fn open_stream(
+        &self,
+        id: &DaemonId,
+        cmd: &str,
+        params: serde_json::Value,
+    ) -> Result<(StreamHandle, mpsc::UnboundedReceiver<DaemonEvent>), DaemonError> {
+        let bridge = self.bridge_for(id).await?;
+        DaemonBridge::open_stream(bridge, cmd, params).await
+    }
+
+    /// Send the file at `path` to the daemon addressed by `id` for
+    /// `session_id`, chunked rather than as one base64 blob. Returns the
+    /// generated transfer id.
+    pub async fn send_file(
+        &self,
+        id: &DaemonId,
+        session_id: &str,
+        path: &std::path::Path,
+    ) -> Result<String, DaemonError> {
+        let bridge = self.bridge_for(id).await?;
+        DaemonBridge::send_file(bridge, session_id, path).await
+    }
+
+    /// Subscribe to events from the daemon addressed by `id`, optionally
+    /// restricted to a set of `evt` names. Unlike the manager's blanket
+    /// aggregated stream (every event from every connection, forwarded
+    /// as-is to the frontend), each call here gets its own independent,
+    /// optionally-filtered feed off that connection's event bus - used by
+    /// the control socket's `subscribe` command so an out-of-process
+    /// client can watch just the events it cares about.
+    pub async fn subscribe(
+        &self,
+        id: &DaemonId,
+        filter: Option<Vec<String>>,
+    ) -> Result<broadcast::Receiver<DaemonEvent>, DaemonError> {
+        let bridge = self.bridge_for(id).await?;
+        let rx = bridge.lock().await.subscribe(filter).await;
+        Ok(rx)
+    }
+
+    /// Whether the daemon addressed by `id` is running.
+    pub async fn is_running(&self, id: &DaemonId) -> Result<bool, DaemonError> {
+        let bridge = self.bridge_for(id).await?;
+        let running = bridge.lock().await.is_running();
+        Ok(running)
+    }
+
+    /// List the status of every connection this manager knows about.
+    pub async fn list(&self) -> Vec<DaemonStatus> {
+        let mut statuses = Vec::new();
+        for (id, managed) in self.daemons.read().await.iter() {
+            let running = managed.bridge.lock().await.is_running();
+            statuses.push(DaemonStatus {
+                id: id.clone(),
+                source: managed.source.to_string(),
+                running,
+            });
+        }
+        statuses
+    }
+
+    /// Stop and forget the daemon addressed by `id`.
+    pub async fn shutdown(&self, id: &DaemonId) -> Result<(), DaemonError> {
+        let managed = self
+            .daemons
+            .write()
+            .await
+            .remove(id)
+            .ok_or(DaemonError::NotRunning)?;
+
+        // `stop()` sleeps for up to 500ms and may block on `Child::kill`/
+        // `Child::wait` while holding the bridge's async mutex, so it can't
+        // run directly on a Tokio worker thread any more than
+        // spawn_supervised/attach can. Run it on a blocking-pool thread
+        // the same way `launch`/`connect` already do.
+        let bridge = managed.bridge;
+        tokio::task::spawn_blocking(move || bridge.blocking_lock().stop())
+            .await
+            .map_err(|e| DaemonError::SendError(format!("stop task panicked: {}", e)))?
+    }
+
+    async fn bridge_for(&self, id: &DaemonId) -> Result<SharedDaemonBridge, DaemonError> {
+        self.daemons
+            .read()
+            .await
+            .get(id)
+            .map(|managed| Arc::clone(&managed.bridge))
+            .ok_or(DaemonError::NotRunning)
+    }
+}
@@ -8,28 +8,52 @@
     windows_subsystem = "windows"
 )]
 
+mod control_socket;
 mod daemon_bridge;
+mod daemon_manager;
+mod settings;
 
-use daemon_bridge::{create_shared_bridge, DaemonBridge, DaemonCommand, DaemonEvent, SharedDaemonBridge};
-use once_cell::sync::OnceCell;
+use daemon_bridge::{
+    ControlEndpoint, DaemonBridge, DaemonCommand, DaemonError, FramingMode, StreamHandle,
+};
+use daemon_manager::{DaemonId, DaemonManager};
 use serde::{Deserialize, Serialize};
+use settings::Settings;
+use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager, State};
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::Mutex;
 use tracing::{error, info, warn};
 
-/// Global daemon bridge instance
-static DAEMON_BRIDGE: OnceCell<SharedDaemonBridge> = OnceCell::new();
-
-/// Event receiver for forwarding daemon events to the frontend
-static EVENT_RECEIVER: OnceCell<Arc<Mutex<Option<mpsc::UnboundedReceiver<DaemonEvent>>>>> =
-    OnceCell::new();
-
 /// Application state
 pub struct AppState {
-    bridge: SharedDaemonBridge,
+    manager: DaemonManager,
     resource_dir: Option<PathBuf>,
+    config_dir: Option<PathBuf>,
+    settings: Mutex<Settings>,
+    /// Open remote-process/PTY streams from `spawn_process`, keyed by
+    /// process id, so later `process_stdin`/`resize_pty`/`kill_process`
+    /// calls can address them.
+    process_streams: Mutex<HashMap<String, StreamHandle>>,
+}
+
+/// A categorized command failure: a stable, machine-readable `code` the
+/// frontend can switch on (e.g. to distinguish "denied" from "cancelled"),
+/// alongside a human-readable `message` for display.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandError {
+    pub code: String,
+    pub message: String,
+}
+
+impl From<&DaemonError> for CommandError {
+    fn from(e: &DaemonError) -> Self {
+        Self {
+            code: e.code().to_string(),
+            message: e.to_string(),
+        }
+    }
 }
 
 /// Response type for Tauri commands
@@ -37,7 +61,7 @@ pub struct AppState {
 pub struct CommandResponse {
     pub success: bool,
     pub data: Option<serde_json::Value>,
-    pub error: Option<String>,
+    pub error: Option<CommandError>,
 }
 
 impl CommandResponse {
@@ -49,112 +73,143 @@ impl CommandResponse {
         }
     }
 
+    /// Build an error response with an opaque `"internal"` code, for
+    /// failures that don't originate from a [`DaemonError`].
     pub fn error(msg: &str) -> Self {
         Self {
             success: false,
             data: None,
-            error: Some(msg.to_string()),
+            error: Some(CommandError {
+                code: "internal".to_string(),
+                message: msg.to_string(),
+            }),
+        }
+    }
+
+    /// Build an error response from a [`DaemonError`], carrying its
+    /// category through as a machine-readable `code`.
+    pub fn daemon_error(e: &DaemonError) -> Self {
+        Self {
+            success: false,
+            data: None,
+            error: Some(e.into()),
         }
     }
 }
 
-/// Start the daemon process
+/// Launch a new supervised daemon process, returning the `connection_id`
+/// it's addressed by. `framing` chooses the stdio wire transport; when not
+/// given, the transport last used (or `LineJson`, the first time) is kept.
 #[tauri::command]
 async fn start_daemon(
-    app: AppHandle,
     state: State<'_, AppState>,
+    framing: Option<FramingMode>,
 ) -> Result<CommandResponse, String> {
-    let mut bridge = state.bridge.lock().await;
-
-    if bridge.is_running() {
-        return Ok(CommandResponse::error("Daemon is already running"));
-    }
-
-    // Find daemon binary
-    let daemon_path = match DaemonBridge::find_daemon_binary(state.resource_dir.clone()) {
-        Ok(path) => path,
-        Err(e) => {
-            error!("Failed to find daemon binary: {}", e);
-            return Ok(CommandResponse::error(&format!(
-                "Daemon binary not found: {}",
-                e
-            )));
-        }
+    // Find daemon binary, preferring a path a previous run already
+    // resolved so auto-detection only runs once per install.
+    let cached_path = state.settings.lock().await.daemon_binary_path.clone();
+    let daemon_path = match cached_path.filter(|p| p.exists()) {
+        Some(path) => path,
+        None => match DaemonBridge::find_daemon_binary(state.resource_dir.clone()) {
+            Ok(path) => path,
+            Err(e) => {
+                error!("Failed to find daemon binary: {}", e);
+                return Ok(CommandResponse::daemon_error(&e));
+            }
+        },
     };
 
-    // Create event channel
-    let (tx, rx) = mpsc::unbounded_channel();
+    let framing = framing.unwrap_or(state.settings.lock().await.framing_mode);
 
-    // Store receiver for the event forwarder
-    if let Some(receiver) = EVENT_RECEIVER.get() {
-        *receiver.lock().await = Some(rx);
+    {
+        let mut settings = state.settings.lock().await;
+        settings.daemon_binary_path = Some(daemon_path.clone());
+        settings.framing_mode = framing;
+        if let Some(config_dir) = &state.config_dir {
+            if let Err(e) = settings.save(config_dir) {
+                warn!("Failed to persist settings: {}", e);
+            }
+        }
     }
 
-    // Spawn daemon
-    if let Err(e) = bridge.spawn(daemon_path.clone(), tx) {
-        error!("Failed to spawn daemon: {}", e);
-        return Ok(CommandResponse::error(&format!(
-            "Failed to spawn daemon: {}",
-            e
-        )));
+    match state.manager.launch(daemon_path.clone(), framing).await {
+        Ok(id) => {
+            info!("Daemon {} launched from: {:?}", id, daemon_path);
+            Ok(CommandResponse::success(serde_json::json!({
+                "status": "started",
+                "connection_id": id.as_str(),
+                "path": daemon_path.to_string_lossy()
+            })))
+        }
+        Err(e) => {
+            error!("Failed to launch daemon: {}", e);
+            Ok(CommandResponse::daemon_error(&e))
+        }
     }
-
-    // Start event forwarder task
-    let app_handle = app.clone();
-    tokio::spawn(async move {
-        forward_daemon_events(app_handle).await;
-    });
-
-    info!("Daemon started from: {:?}", daemon_path);
-    Ok(CommandResponse::success(serde_json::json!({
-        "status": "started",
-        "path": daemon_path.to_string_lossy()
-    })))
 }
 
-/// Stop the daemon process
+/// Stop the daemon connection addressed by `connection_id`.
 #[tauri::command]
-async fn stop_daemon(state: State<'_, AppState>) -> Result<CommandResponse, String> {
-    let mut bridge = state.bridge.lock().await;
-
-    if !bridge.is_running() {
-        return Ok(CommandResponse::error("Daemon is not running"));
+async fn stop_daemon(
+    state: State<'_, AppState>,
+    connection_id: String,
+) -> Result<CommandResponse, String> {
+    let id = DaemonId::from_string(connection_id);
+
+    match state.manager.shutdown(&id).await {
+        Ok(()) => {
+            info!("Daemon {} stopped", id);
+            Ok(CommandResponse::success(serde_json::json!({
+                "status": "stopped"
+            })))
+        }
+        Err(e) => {
+            error!("Failed to stop daemon {}: {}", id, e);
+            Ok(CommandResponse::daemon_error(&e))
+        }
     }
+}
 
-    if let Err(e) = bridge.stop() {
-        error!("Failed to stop daemon: {}", e);
-        return Ok(CommandResponse::error(&format!(
-            "Failed to stop daemon: {}",
-            e
-        )));
+/// Check if the daemon connection addressed by `connection_id` is running.
+#[tauri::command]
+async fn daemon_status(
+    state: State<'_, AppState>,
+    connection_id: String,
+) -> Result<CommandResponse, String> {
+    let id = DaemonId::from_string(connection_id);
+
+    match state.manager.is_running(&id).await {
+        Ok(running) => Ok(CommandResponse::success(serde_json::json!({
+            "running": running
+        }))),
+        Err(e) => Ok(CommandResponse::success(serde_json::json!({
+            "running": false,
+            "error": e.to_string()
+        }))),
     }
-
-    info!("Daemon stopped");
-    Ok(CommandResponse::success(serde_json::json!({
-        "status": "stopped"
-    })))
 }
 
-/// Check if the daemon is running
+/// List every daemon connection the manager currently knows about.
 #[tauri::command]
-async fn daemon_status(state: State<'_, AppState>) -> Result<CommandResponse, String> {
-    let mut bridge = state.bridge.lock().await;
-    let running = bridge.is_running();
-
-    Ok(CommandResponse::success(serde_json::json!({
-        "running": running
-    })))
+async fn list_daemons(state: State<'_, AppState>) -> Result<CommandResponse, String> {
+    let statuses = state.manager.list().await;
+    Ok(CommandResponse::success(
+        serde_json::to_value(statuses).unwrap_or_default(),
+    ))
 }
 
-/// Start a kamune server
+/// Start a kamune server on the connection addressed by `connection_id`.
+/// `timeout_ms` overrides the default command deadline, if given.
 #[tauri::command]
 async fn start_server(
     state: State<'_, AppState>,
+    connection_id: String,
     addr: String,
     storage_path: Option<String>,
     no_passphrase: Option<bool>,
+    timeout_ms: Option<u64>,
 ) -> Result<CommandResponse, String> {
-    let bridge = Arc::clone(&state.bridge);
+    let id = DaemonId::from_string(connection_id);
 
     let cmd = DaemonCommand::new(
         "start_server",
@@ -165,27 +220,31 @@ async fn start_server(
         }),
     );
 
-    match DaemonBridge::send_command_async(bridge, cmd).await {
+    match state
+        .manager
+        .send_to(&id, cmd, timeout_ms.map(Duration::from_millis))
+        .await
+    {
         Ok(event) => Ok(CommandResponse::success(event.data)),
         Err(e) => {
             error!("Failed to start server: {}", e);
-            Ok(CommandResponse::error(&format!(
-                "Failed to start server: {}",
-                e
-            )))
+            Ok(CommandResponse::daemon_error(&e))
         }
     }
 }
 
-/// Dial a remote server
+/// Dial a remote server on the connection addressed by `connection_id`.
+/// `timeout_ms` overrides the default command deadline, if given.
 #[tauri::command]
 async fn dial(
     state: State<'_, AppState>,
+    connection_id: String,
     addr: String,
     storage_path: Option<String>,
     no_passphrase: Option<bool>,
+    timeout_ms: Option<u64>,
 ) -> Result<CommandResponse, String> {
-    let bridge = Arc::clone(&state.bridge);
+    let id = DaemonId::from_string(connection_id);
 
     let cmd = DaemonCommand::new(
         "dial",
@@ -196,23 +255,31 @@ async fn dial(
         }),
     );
 
-    match DaemonBridge::send_command_async(bridge, cmd).await {
+    match state
+        .manager
+        .send_to(&id, cmd, timeout_ms.map(Duration::from_millis))
+        .await
+    {
         Ok(event) => Ok(CommandResponse::success(event.data)),
         Err(e) => {
             error!("Failed to dial: {}", e);
-            Ok(CommandResponse::error(&format!("Failed to dial: {}", e)))
+            Ok(CommandResponse::daemon_error(&e))
         }
     }
 }
 
-/// Send a message on a session
+/// Send a message on a session over the connection addressed by
+/// `connection_id`. `timeout_ms` overrides the default command deadline,
+/// if given.
 #[tauri::command]
 async fn send_message(
     state: State<'_, AppState>,
+    connection_id: String,
     session_id: String,
     message: String,
+    timeout_ms: Option<u64>,
 ) -> Result<CommandResponse, String> {
-    let bridge = Arc::clone(&state.bridge);
+    let id = DaemonId::from_string(connection_id);
 
     // Base64 encode the message
     let data_base64 = base64::Engine::encode(
@@ -228,44 +295,46 @@ async fn send_message(
         }),
     );
 
-    match DaemonBridge::send_command_async(bridge, cmd).await {
+    match state
+        .manager
+        .send_to(&id, cmd, timeout_ms.map(Duration::from_millis))
+        .await
+    {
         Ok(event) => Ok(CommandResponse::success(event.data)),
         Err(e) => {
             error!("Failed to send message: {}", e);
-            Ok(CommandResponse::error(&format!(
-                "Failed to send message: {}",
-                e
-            )))
+            Ok(CommandResponse::daemon_error(&e))
         }
     }
 }
 
-/// List active sessions
+/// List active sessions on the connection addressed by `connection_id`.
 #[tauri::command]
-async fn list_sessions(state: State<'_, AppState>) -> Result<CommandResponse, String> {
-    let bridge = Arc::clone(&state.bridge);
+async fn list_sessions(
+    state: State<'_, AppState>,
+    connection_id: String,
+) -> Result<CommandResponse, String> {
+    let id = DaemonId::from_string(connection_id);
 
     let cmd = DaemonCommand::new("list_sessions", serde_json::json!({}));
 
-    match DaemonBridge::send_command_async(bridge, cmd).await {
+    match state.manager.send_to(&id, cmd, None).await {
         Ok(event) => Ok(CommandResponse::success(event.data)),
         Err(e) => {
             error!("Failed to list sessions: {}", e);
-            Ok(CommandResponse::error(&format!(
-                "Failed to list sessions: {}",
-                e
-            )))
+            Ok(CommandResponse::daemon_error(&e))
         }
     }
 }
 
-/// Close a session
+/// Close a session on the connection addressed by `connection_id`.
 #[tauri::command]
 async fn close_session(
     state: State<'_, AppState>,
+    connection_id: String,
     session_id: String,
 ) -> Result<CommandResponse, String> {
-    let bridge = Arc::clone(&state.bridge);
+    let id = DaemonId::from_string(connection_id);
 
     let cmd = DaemonCommand::new(
         "close_session",
@@ -274,48 +343,285 @@ async fn close_session(
         }),
     );
 
-    match DaemonBridge::send_command_async(bridge, cmd).await {
+    match state.manager.send_to(&id, cmd, None).await {
         Ok(event) => Ok(CommandResponse::success(event.data)),
         Err(e) => {
             error!("Failed to close session: {}", e);
-            Ok(CommandResponse::error(&format!(
-                "Failed to close session: {}",
-                e
-            )))
+            Ok(CommandResponse::daemon_error(&e))
+        }
+    }
+}
+
+/// Send a file to a session over the connection addressed by
+/// `connection_id`, chunked rather than as one base64 blob. Returns the
+/// `transfer_id` progress/completion events will be tagged with.
+#[tauri::command]
+async fn send_file(
+    state: State<'_, AppState>,
+    connection_id: String,
+    session_id: String,
+    path: String,
+) -> Result<CommandResponse, String> {
+    let id = DaemonId::from_string(connection_id);
+
+    match state
+        .manager
+        .send_file(&id, &session_id, std::path::Path::new(&path))
+        .await
+    {
+        Ok(transfer_id) => Ok(CommandResponse::success(serde_json::json!({
+            "transfer_id": transfer_id
+        }))),
+        Err(e) => {
+            error!("Failed to send file: {}", e);
+            Ok(CommandResponse::daemon_error(&e))
+        }
+    }
+}
+
+/// Spawn a remote process (or, with `pty: true`, an interactive PTY shell)
+/// on a session over the connection addressed by `connection_id`,
+/// returning the `process_id` that `process_stdin`/`resize_pty`/
+/// `kill_process` address it by. Its output streams to the frontend as
+/// `daemon:<connection_id>:process_stdout`/`daemon:<connection_id>:process_stderr`
+/// events tagged with this id, terminated by
+/// `daemon:<connection_id>:process_exit`, through the same aggregated
+/// forwarder as every other daemon event.
+#[tauri::command]
+async fn spawn_process(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    connection_id: String,
+    session_id: String,
+    command: String,
+    args: Vec<String>,
+    pty: Option<bool>,
+) -> Result<CommandResponse, String> {
+    let id = DaemonId::from_string(connection_id);
+
+    let (handle, mut rx) = match state
+        .manager
+        .open_stream(
+            &id,
+            "spawn_process",
+            serde_json::json!({
+                "session_id": session_id,
+                "command": command,
+                "args": args,
+                "pty": pty.unwrap_or(false)
+            }),
+        )
+        .await
+    {
+        Ok(pair) => pair,
+        Err(e) => {
+            error!("Failed to spawn process: {}", e);
+            return Ok(CommandResponse::daemon_error(&e));
+        }
+    };
+
+    let process_id = handle.id().to_string();
+    state
+        .process_streams
+        .lock()
+        .await
+        .insert(process_id.clone(), handle);
+
+    // Forget the handle once the stream ends; its output already reached
+    // the frontend through the normal daemon event forwarder.
+    let cleanup_id = process_id.clone();
+    tokio::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            if event.evt == "stream-end" {
+                break;
+            }
+        }
+        app.state::<AppState>()
+            .process_streams
+            .lock()
+            .await
+            .remove(&cleanup_id);
+    });
+
+    Ok(CommandResponse::success(serde_json::json!({
+        "process_id": process_id
+    })))
+}
+
+/// Write `data` to a spawned process's stdin.
+#[tauri::command]
+async fn process_stdin(
+    state: State<'_, AppState>,
+    process_id: String,
+    data: String,
+) -> Result<CommandResponse, String> {
+    let streams = state.process_streams.lock().await;
+    let Some(handle) = streams.get(&process_id) else {
+        return Ok(CommandResponse::error("no such process"));
+    };
+
+    match handle.write(data.as_bytes()).await {
+        Ok(()) => Ok(CommandResponse::success(serde_json::json!({
+            "status": "sent"
+        }))),
+        Err(e) => {
+            error!("Failed to write process stdin: {}", e);
+            Ok(CommandResponse::daemon_error(&e))
+        }
+    }
+}
+
+/// Resize the PTY backing a process spawned with `pty: true`.
+#[tauri::command]
+async fn resize_pty(
+    state: State<'_, AppState>,
+    process_id: String,
+    cols: u16,
+    rows: u16,
+) -> Result<CommandResponse, String> {
+    let streams = state.process_streams.lock().await;
+    let Some(handle) = streams.get(&process_id) else {
+        return Ok(CommandResponse::error("no such process"));
+    };
+
+    match handle.resize(cols, rows).await {
+        Ok(()) => Ok(CommandResponse::success(serde_json::json!({
+            "status": "resized"
+        }))),
+        Err(e) => {
+            error!("Failed to resize pty for process {}: {}", process_id, e);
+            Ok(CommandResponse::daemon_error(&e))
+        }
+    }
+}
+
+/// Kill a spawned process and forget it.
+#[tauri::command]
+async fn kill_process(
+    state: State<'_, AppState>,
+    process_id: String,
+) -> Result<CommandResponse, String> {
+    let Some(handle) = state.process_streams.lock().await.remove(&process_id) else {
+        return Ok(CommandResponse::error("no such process"));
+    };
+
+    match handle.close().await {
+        Ok(()) => Ok(CommandResponse::success(serde_json::json!({
+            "status": "killed"
+        }))),
+        Err(e) => {
+            error!("Failed to kill process {}: {}", process_id, e);
+            Ok(CommandResponse::daemon_error(&e))
         }
     }
 }
 
-/// Forward daemon events to the frontend
-async fn forward_daemon_events(app: AppHandle) {
-    let receiver = match EVENT_RECEIVER.get() {
-        Some(r) => r,
-        None => {
-            warn!("Event receiver not initialized");
-            return;
+/// Cancel an in-flight command, by the id returned from the command that
+/// started it, on the connection addressed by `connection_id`.
+#[tauri::command]
+async fn cancel_command(
+    state: State<'_, AppState>,
+    connection_id: String,
+    id: String,
+) -> Result<CommandResponse, String> {
+    let connection = DaemonId::from_string(connection_id);
+
+    match state.manager.cancel(&connection, &id).await {
+        Ok(()) => Ok(CommandResponse::success(serde_json::json!({
+            "id": id,
+            "status": "cancelling"
+        }))),
+        Err(e) => {
+            error!("Failed to cancel command {}: {}", id, e);
+            Ok(CommandResponse::daemon_error(&e))
         }
+    }
+}
+
+/// Probe a control endpoint (Unix socket path or TCP addr) to see whether a
+/// daemon is already listening there, without attaching to it.
+#[tauri::command]
+async fn check_daemon_running(endpoint: String) -> Result<CommandResponse, String> {
+    let parsed = match ControlEndpoint::parse(&endpoint) {
+        Ok(endpoint) => endpoint,
+        Err(e) => return Ok(CommandResponse::daemon_error(&e)),
     };
 
-    let mut rx = match receiver.lock().await.take() {
-        Some(rx) => rx,
-        None => {
-            warn!("No event receiver available");
-            return;
+    // `probe_endpoint` blocks on a connect (bounded, but still a blocking
+    // syscall) and must not run directly on a Tokio worker thread, the same
+    // hazard `attach_daemon` was fixed for.
+    let running = tokio::task::spawn_blocking(move || daemon_bridge::probe_endpoint(&parsed))
+        .await
+        .unwrap_or(false);
+    Ok(CommandResponse::success(serde_json::json!({
+        "endpoint": endpoint,
+        "running": running
+    })))
+}
+
+/// Attach to an already-running daemon at `endpoint` instead of spawning a
+/// new one, returning the `connection_id` it's addressed by. `framing`
+/// chooses the stdio wire transport the daemon is expected to speak; when
+/// not given, the transport last used (or `LineJson`, the first time) is
+/// kept.
+#[tauri::command]
+async fn attach_daemon(
+    state: State<'_, AppState>,
+    endpoint: String,
+    framing: Option<FramingMode>,
+) -> Result<CommandResponse, String> {
+    let parsed = match ControlEndpoint::parse(&endpoint) {
+        Ok(endpoint) => endpoint,
+        Err(e) => return Ok(CommandResponse::daemon_error(&e)),
+    };
+
+    let framing = framing.unwrap_or(state.settings.lock().await.framing_mode);
+
+    let id = match state.manager.connect(parsed, framing).await {
+        Ok(id) => id,
+        Err(e) => {
+            error!("Failed to attach to daemon at {}: {}", endpoint, e);
+            return Ok(CommandResponse::daemon_error(&e));
         }
     };
 
+    {
+        let mut settings = state.settings.lock().await;
+        settings.last_endpoint = Some(endpoint.clone());
+        settings.framing_mode = framing;
+        if let Some(config_dir) = &state.config_dir {
+            if let Err(e) = settings.save(config_dir) {
+                warn!("Failed to persist settings: {}", e);
+            }
+        }
+    }
+
+    info!("Attached to daemon {} at: {}", id, endpoint);
+    Ok(CommandResponse::success(serde_json::json!({
+        "status": "attached",
+        "connection_id": id.as_str(),
+        "endpoint": endpoint
+    })))
+}
+
+/// Forward every managed daemon's events to the frontend, tagged with the
+/// connection they originated from.
+async fn forward_daemon_events(
+    app: AppHandle,
+    mut events: tokio::sync::mpsc::UnboundedReceiver<daemon_manager::TaggedDaemonEvent>,
+) {
     info!("Starting daemon event forwarder");
 
-    while let Some(event) = rx.recv().await {
-        // Map daemon events to Tauri events
-        let event_name = format!("daemon:{}", event.evt);
+    while let Some(tagged) = events.recv().await {
+        // Map daemon events to connection-scoped Tauri events
+        let event_name = format!("daemon:{}:{}", tagged.daemon_id, tagged.event.evt);
 
-        if let Err(e) = app.emit(&event_name, &event) {
+        if let Err(e) = app.emit(&event_name, &tagged) {
             error!("Failed to emit event {}: {}", event_name, e);
         }
 
         // Also emit a generic daemon event for catch-all handlers
-        if let Err(e) = app.emit("daemon:event", &event) {
+        if let Err(e) = app.emit("daemon:event", &tagged) {
             error!("Failed to emit generic daemon event: {}", e);
         }
     }
@@ -334,27 +640,64 @@ fn main() {
 
     info!("Starting Kamune Desktop Application");
 
-    // Create shared bridge
-    let bridge = create_shared_bridge();
-    DAEMON_BRIDGE.set(Arc::clone(&bridge)).ok();
-
-    // Initialize event receiver holder
-    EVENT_RECEIVER
-        .set(Arc::new(Mutex::new(None)))
-        .ok();
+    // Create the manager that will juggle every daemon connection and its
+    // aggregated, connection-tagged event stream.
+    let (manager, aggregated_rx) = DaemonManager::new();
 
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
-        .setup(|app| {
+        .setup(move |app| {
             // Get resource directory for finding bundled binaries
             let resource_dir = app.path().resource_dir().ok();
 
             info!("Resource directory: {:?}", resource_dir);
 
+            // Get config directory for the persisted settings store
+            let config_dir = app.path().app_config_dir().ok();
+            let settings = config_dir
+                .as_ref()
+                .map(|dir| Settings::load(dir))
+                .unwrap_or_default();
+
+            // Start the single, long-lived forwarder that tags and emits
+            // events from every connection the manager juggles.
+            let app_handle = app.handle().clone();
+            tokio::spawn(async move {
+                forward_daemon_events(app_handle, aggregated_rx).await;
+            });
+
+            // Start the control socket so a companion CLI can drive any
+            // connection the GUI has open.
+            let control_manager = manager.clone();
+            #[cfg(unix)]
+            {
+                if let Some(dir) = &config_dir {
+                    let socket_path = control_socket::default_socket_path(dir);
+                    tokio::spawn(async move {
+                        if let Err(e) = control_socket::serve(control_manager, socket_path).await
+                        {
+                            error!("Control socket failed: {}", e);
+                        }
+                    });
+                }
+            }
+            #[cfg(windows)]
+            {
+                tokio::spawn(async move {
+                    let pipe_name = control_socket::default_pipe_name();
+                    if let Err(e) = control_socket::serve(control_manager, pipe_name).await {
+                        error!("Control socket failed: {}", e);
+                    }
+                });
+            }
+
             // Initialize app state
             let state = AppState {
-                bridge: DAEMON_BRIDGE.get().unwrap().clone(),
+                manager,
                 resource_dir,
+                config_dir,
+                settings: Mutex::new(settings),
+                process_streams: Mutex::new(HashMap::new()),
             };
 
             app.manage(state);
@@ -365,11 +708,20 @@ fn main() {
             start_daemon,
             stop_daemon,
             daemon_status,
+            list_daemons,
             start_server,
             dial,
             send_message,
             list_sessions,
             close_session,
+            send_file,
+            spawn_process,
+            process_stdin,
+            resize_pty,
+            kill_process,
+            cancel_command,
+            check_daemon_running,
+            attach_daemon,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,46 @@
+//! Persisted App Settings
+//!
+//! Small, best-effort settings store for choices that should survive
+//! application restarts: the resolved daemon binary path (so auto-detection
+//! only runs once) and the last control endpoint used to `attach` to a
+//! running daemon.
+
+use crate::daemon_bridge::FramingMode;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// App settings persisted as JSON in the app's config directory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Settings {
+    /// Daemon binary path resolved by a previous `find_daemon_binary` call.
+    pub daemon_binary_path: Option<PathBuf>,
+    /// Control endpoint (Unix socket path or TCP addr) last used to attach
+    /// to a running daemon.
+    pub last_endpoint: Option<String>,
+    /// Wire transport last chosen for `start_daemon`/`attach_daemon`, so a
+    /// caller that doesn't specify one keeps using the same transport
+    /// across restarts instead of silently falling back to `LineJson`.
+    pub framing_mode: FramingMode,
+}
+
+impl Settings {
+    /// Load settings from `config_dir`, falling back to defaults if the
+    /// file is missing or unreadable.
+    pub fn load(config_dir: &Path) -> Self {
+        let path = config_dir.join(SETTINGS_FILE);
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Save settings to `config_dir`, creating the directory if needed.
+    pub fn save(&self, config_dir: &Path) -> std::io::Result<()> {
+        std::fs::create_dir_all(config_dir)?;
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(config_dir.join(SETTINGS_FILE), contents)
+    }
+}